@@ -0,0 +1,140 @@
+//! OS のアクセシビリティ設定（視差効果を減らす・ハイコントラスト）を検出するモジュール
+//! - Windows: `SystemParametersInfoW`（クライアント領域のアニメーション、ハイコントラスト）
+//! - macOS: `defaults read com.apple.universalaccess`（reduceMotion / increaseContrast）
+//! - Linux: `gsettings`（GNOME の enable-animations / high-contrast）
+//! 変更通知の仕組みは OS ごとに異なるため、一定間隔で読み直して変わった時にイベントを送信する
+
+use std::time::Duration;
+
+use log::{error, info};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// アクセシビリティ設定が変わった時に送信するイベント名
+pub const ACCESSIBILITY_CHANGED_EVENT: &str = "accessibility-changed";
+
+/// OS の設定を読み直す間隔
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// OS のアクセシビリティ設定
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccessibilityPreferences {
+  pub reduced_motion: bool, // アニメーションを減らす
+  pub high_contrast: bool,  // ハイコントラスト（コントラストを上げる）
+}
+
+/// `defaults` / `gsettings` の出力を真偽値として読む（読めない場合は None）
+fn parse_flag(output: &str) -> Option<bool> {
+  match output.trim().trim_matches('\'') {
+    "1" | "true" => Some(true),
+    "0" | "false" => Some(false),
+    _ => None,
+  }
+}
+
+/// コマンドを実行し、標準出力を真偽値として読む（コマンドがない・失敗した場合は None）
+#[cfg(not(windows))]
+fn read_flag(program: &str, args: &[&str]) -> Option<bool> {
+  let output = std::process::Command::new(program).args(args).output().ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  parse_flag(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// 現在の OS のアクセシビリティ設定を取得する
+#[cfg(windows)]
+pub fn detect() -> AccessibilityPreferences {
+  use std::ffi::c_void;
+
+  use windows_sys::Win32::UI::{
+    Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW},
+    WindowsAndMessaging::{SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST},
+  };
+
+  let mut animation: i32 = 1;
+  let mut contrast = HIGHCONTRASTW {
+    cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+    dwFlags: 0,
+    lpszDefaultScheme: std::ptr::null_mut(),
+  };
+  // SAFETY: どちらも呼び出し中だけ有効なローカル変数へのポインタを渡し、サイズは各 SPI の仕様どおり
+  let animation_read = unsafe { SystemParametersInfoW(SPI_GETCLIENTAREAANIMATION, 0, &mut animation as *mut i32 as *mut c_void, 0) } != 0;
+  let contrast_read = unsafe { SystemParametersInfoW(SPI_GETHIGHCONTRAST, contrast.cbSize, &mut contrast as *mut HIGHCONTRASTW as *mut c_void, 0) } != 0;
+
+  AccessibilityPreferences {
+    reduced_motion: animation_read && animation == 0,
+    high_contrast: contrast_read && contrast.dwFlags & HCF_HIGHCONTRASTON != 0,
+  }
+}
+
+/// 現在の OS のアクセシビリティ設定を取得する
+#[cfg(target_os = "macos")]
+pub fn detect() -> AccessibilityPreferences {
+  AccessibilityPreferences {
+    reduced_motion: read_flag("defaults", &["read", "com.apple.universalaccess", "reduceMotion"]).unwrap_or(false),
+    high_contrast: read_flag("defaults", &["read", "com.apple.universalaccess", "increaseContrast"]).unwrap_or(false),
+  }
+}
+
+/// 現在の OS のアクセシビリティ設定を取得する
+/// GNOME 以外のデスクトップなど `gsettings` で読めない場合は既定値（どちらも無効）とする
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn detect() -> AccessibilityPreferences {
+  AccessibilityPreferences {
+    // enable-animations が false の場合にアニメーションを減らす
+    reduced_motion: read_flag("gsettings", &["get", "org.gnome.desktop.interface", "enable-animations"]).is_some_and(|enabled| !enabled),
+    high_contrast: read_flag("gsettings", &["get", "org.gnome.desktop.a11y.interface", "high-contrast"]).unwrap_or(false),
+  }
+}
+
+/// アクセシビリティ設定の監視を開始する
+/// 一定間隔で OS の設定を読み直し、変わった場合に `accessibility-changed` イベントを送信する
+pub fn start_accessibility_watch(app: AppHandle) {
+  std::thread::spawn(move || {
+    let mut current = detect();
+    info!("アクセシビリティ設定を検出しました: {:?}", current);
+    loop {
+      std::thread::sleep(POLL_INTERVAL);
+      let latest = detect();
+      if latest == current {
+        continue;
+      }
+      info!("アクセシビリティ設定が変更されました: {:?}", latest);
+      current = latest;
+      if let Err(e) = app.emit(ACCESSIBILITY_CHANGED_EVENT, latest) {
+        error!("{} イベントの送信に失敗しました: {}", ACCESSIBILITY_CHANGED_EVENT, e);
+      }
+    }
+  });
+}
+
+/// OS のアクセシビリティ設定を取得するコマンド
+/// 変更は `accessibility-changed` イベントで通知する
+///
+/// # 戻り値
+/// * `AccessibilityPreferences` - アニメーションを減らすか、ハイコントラストか
+#[tauri::command]
+pub fn get_accessibility_preferences() -> AccessibilityPreferences {
+  detect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_flag_reads_defaults_and_gsettings_output() {
+    assert_eq!(parse_flag("1\n"), Some(true));
+    assert_eq!(parse_flag("0\n"), Some(false));
+    assert_eq!(parse_flag("true\n"), Some(true));
+    assert_eq!(parse_flag("false"), Some(false));
+  }
+
+  #[test]
+  fn parse_flag_rejects_unexpected_output() {
+    for output in ["", "yes", "'HighContrast'", "The domain/default pair does not exist"] {
+      assert_eq!(parse_flag(output), None, "{}", output);
+    }
+  }
+}
@@ -1,3 +1,39 @@
+use std::path::Path;
+
+use log::error;
+use serde::Deserialize;
+use tauri::{AppHandle, Manager, Window};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::{
+  safe_opener,
+  store_manager::{
+    self,
+    ColumnDisplayPreferences,
+    CustomTheme,
+    DialogCategory,
+    DialogDirectories,
+    Keybinding,
+    LayoutPreset,
+    MainPanelLayout,
+    ProfileList,
+    ProjectConfig,
+    ProjectSettings,
+    ProjectSort,
+    RecentProject,
+    ResetScope,
+    SearchFilters,
+    SearchResult,
+    SettingsSync,
+    SyncOutcome,
+    SyncPreference,
+    TagSummary,
+    WindowState,
+    Workspace,
+  },
+  system_monitor::{self, PerformanceMode},
+};
+
 /// 基本的な挨拶機能を提供するコマンド（開発テスト用）
 /// フロントエンド（JavaScript）から呼び出し可能なRust関数
 ///
@@ -10,3 +46,766 @@
 pub fn greet(name: &str) -> String {
   format!("Hello, {}! You've been greeted from Rust!", name)
 }
+
+/// 設定ディレクトリを取得するコマンド
+/// `--config-dir` 引数・環境変数 `D4CS_CONFIG_DIR`・ポータブルモードを反映した実際の保存先を返す
+/// フロントエンドは設定ファイルのパスを自前で組み立てず、このコマンドの結果を使う
+///
+/// # 戻り値
+/// * 設定ディレクトリの絶対パス
+#[tauri::command]
+pub fn get_config_dir() -> Result<String, String> {
+  store_manager::resolve_config_dir().map(|dir| dir.to_string_lossy().to_string()).map_err(|e| e.to_string())
+}
+
+/// ポータブルモード（実行ファイルの隣に `portable.flag` がある）で起動しているかを取得するコマンド
+/// ポータブルモードでは、フロントエンドも既定の保存先にユーザーフォルダではなく設定ディレクトリ（`data/`）を使う
+///
+/// # 戻り値
+/// * ポータブルモードなら true
+#[tauri::command]
+pub fn is_portable_mode() -> bool {
+  store_manager::is_portable()
+}
+
+/// パフォーマンスモードを取得するコマンド
+///
+/// # 戻り値
+/// * 現在のパフォーマンスモード（"performance" / "balanced" / "quiet"）
+#[tauri::command]
+pub fn get_performance_mode(app: AppHandle) -> Result<PerformanceMode, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::load_performance_mode(&app, &config_dir).map_err(|e| format!("パフォーマンスモードの取得に失敗しました: {}", e))
+}
+
+/// パフォーマンスモードを切り替えるコマンド
+/// 監視間隔へ即時反映し、設定ファイルへ永続化する
+///
+/// # 引数
+/// * `mode` - 切り替え先のモード（"performance" / "balanced" / "quiet"）
+#[tauri::command]
+pub fn set_performance_mode(app: AppHandle, mode: PerformanceMode) -> Result<(), String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  system_monitor::apply_performance_mode(mode);
+  store_manager::save_performance_mode(&app, &config_dir, mode).map_err(|e| {
+    error!("パフォーマンスモードの保存に失敗しました: {}", e);
+    format!("パフォーマンスモードの保存に失敗しました: {}", e)
+  })
+}
+
+/// 保存されているレイアウトプリセットの一覧を取得するコマンド
+///
+/// # 戻り値
+/// * プリセット名とパネルレイアウトの一覧
+#[tauri::command]
+pub fn list_layout_presets(app: AppHandle) -> Result<Vec<LayoutPreset>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::load_layout_presets(&app, &config_dir).map_err(|e| format!("レイアウトプリセットの取得に失敗しました: {}", e))
+}
+
+/// 現在のメインパネルレイアウトを名前付きプリセットとして保存するコマンド
+///
+/// # 引数
+/// * `name` - プリセット名（同名があれば上書き）
+///
+/// # 戻り値
+/// * 保存後のプリセット一覧
+#[tauri::command]
+pub fn save_layout_preset(app: AppHandle, name: String) -> Result<Vec<LayoutPreset>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::save_layout_preset(&app, &config_dir, &name).map_err(|e| format!("レイアウトプリセットの保存に失敗しました: {}", e))
+}
+
+/// 名前付きプリセットをメインパネルへ適用するコマンド
+///
+/// # 引数
+/// * `name` - 適用するプリセット名
+///
+/// # 戻り値
+/// * 適用後のメインパネルレイアウト
+#[tauri::command]
+pub fn apply_layout_preset(app: AppHandle, name: String) -> Result<MainPanelLayout, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::apply_layout_preset(&app, &config_dir, &name).map_err(|e| format!("レイアウトプリセットの適用に失敗しました: {}", e))
+}
+
+/// 名前付きプリセットを削除するコマンド
+///
+/// # 引数
+/// * `name` - 削除するプリセット名
+///
+/// # 戻り値
+/// * 削除後のプリセット一覧
+#[tauri::command]
+pub fn delete_layout_preset(app: AppHandle, name: String) -> Result<Vec<LayoutPreset>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::delete_layout_preset(&app, &config_dir, &name).map_err(|e| format!("レイアウトプリセットの削除に失敗しました: {}", e))
+}
+
+/// 列構成に対応する列表示設定を取得するコマンド
+///
+/// # 引数
+/// * `columns` - 開いたファイルの列名（順序込みでスキーマとして扱う）
+///
+/// # 戻り値
+/// * 保存済みの列表示設定（未保存なら None）
+#[tauri::command]
+pub fn get_column_preferences(app: AppHandle, columns: Vec<String>) -> Result<Option<ColumnDisplayPreferences>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::load_column_preferences(&app, &config_dir, &columns).map_err(|e| format!("列表示設定の取得に失敗しました: {}", e))
+}
+
+/// 列構成に対応する列表示設定を保存するコマンド
+///
+/// # 引数
+/// * `columns` - 列名（順序込みでスキーマとして扱う）
+/// * `preferences` - 列幅・非表示列・固定列・表示フォーマット
+#[tauri::command]
+pub fn save_column_preferences(app: AppHandle, columns: Vec<String>, preferences: ColumnDisplayPreferences) -> Result<(), String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::save_column_preferences(&app, &config_dir, &columns, preferences).map_err(|e| format!("列表示設定の保存に失敗しました: {}", e))
+}
+
+/// プロジェクト一覧を取得するコマンド
+///
+/// # 引数
+/// * `sort` - 並び順（"name" / "created_at" / "last_opened_at"、省略時は名前順）
+/// * `pinned_first` - ピン留めしたプロジェクトを先頭にするかどうか（省略時は true）
+///
+/// # 戻り値
+/// * 登録済みプロジェクトの一覧
+#[tauri::command]
+pub fn list_projects(app: AppHandle, sort: Option<ProjectSort>, pinned_first: Option<bool>) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  let mut projects = store_manager::load_projects(&app, &config_dir).map_err(|e| format!("プロジェクト一覧の取得に失敗しました: {}", e))?;
+  store_manager::sort_projects(&mut projects, sort.unwrap_or_default());
+  if pinned_first.unwrap_or(true) {
+    store_manager::pinned_first(&mut projects);
+  }
+  Ok(projects)
+}
+
+/// プロジェクトを一覧に追加するコマンド
+///
+/// # 引数
+/// * `project` - 追加するプロジェクト（名前は一意）
+///
+/// # 戻り値
+/// * 追加後のプロジェクト一覧
+#[tauri::command]
+pub fn add_project(app: AppHandle, project: ProjectConfig) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::add_project(&app, &config_dir, project).map_err(|e| format!("プロジェクトの追加に失敗しました: {}", e))
+}
+
+/// 一覧内のプロジェクトを更新するコマンド
+///
+/// # 引数
+/// * `name` - 更新対象のプロジェクト名
+/// * `project` - 更新後のプロジェクト（名前の変更も可）
+///
+/// # 戻り値
+/// * 更新後のプロジェクト一覧
+#[tauri::command]
+pub fn update_project(app: AppHandle, name: String, project: ProjectConfig) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::update_project(&app, &config_dir, &name, project).map_err(|e| format!("プロジェクトの更新に失敗しました: {}", e))
+}
+
+/// プロジェクトを一覧から削除するコマンド
+/// プロジェクトフォルダ自体は削除しない
+///
+/// # 引数
+/// * `name` - 削除するプロジェクト名
+///
+/// # 戻り値
+/// * 削除後のプロジェクト一覧
+#[tauri::command]
+pub fn remove_project(app: AppHandle, name: String) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::remove_project(&app, &config_dir, &name).map_err(|e| format!("プロジェクトの削除に失敗しました: {}", e))
+}
+
+/// 最近開いたプロジェクト一覧を取得するコマンド
+///
+/// # 戻り値
+/// * 最近開いたプロジェクト（新しい順）
+#[tauri::command]
+pub fn list_recent_projects(app: AppHandle) -> Result<Vec<RecentProject>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::load_recent_projects(&app, &config_dir).map_err(|e| format!("最近開いたプロジェクトの取得に失敗しました: {}", e))
+}
+
+/// プロジェクトを最近開いた一覧に記録するコマンド
+/// 重複は先頭へ移動し、件数は自動で上限に収める
+///
+/// # 引数
+/// * `name` - プロジェクト名
+/// * `filepath` - プロジェクトの保存パス
+///
+/// # 戻り値
+/// * 更新後の一覧（新しい順）
+#[tauri::command]
+pub fn push_recent_project(app: AppHandle, name: String, filepath: String) -> Result<Vec<RecentProject>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::push_recent_project(&app, &config_dir, &name, &filepath).map_err(|e| format!("最近開いたプロジェクトの記録に失敗しました: {}", e))
+}
+
+/// 最近開いた一覧のプロジェクトを現在のプロジェクトとして開くコマンド
+///
+/// # 引数
+/// * `filepath` - 開くプロジェクトの保存パス
+///
+/// # 戻り値
+/// * 現在のプロジェクトになったプロジェクト設定
+#[tauri::command]
+pub fn open_recent_project(app: AppHandle, filepath: String) -> Result<ProjectConfig, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::open_recent_project(&app, &config_dir, &filepath).map_err(|e| format!("プロジェクトを開けませんでした: {}", e))
+}
+
+/// 最近開いたプロジェクト一覧をクリアするコマンド
+#[tauri::command]
+pub fn clear_recent_projects(app: AppHandle) -> Result<(), String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::clear_recent_projects(&app, &config_dir).map_err(|e| format!("最近開いたプロジェクトのクリアに失敗しました: {}", e))
+}
+
+/// 現在のウィンドウ状態（サイズ・位置・最大化・テーマ・パネルレイアウト）を保存するコマンド
+///
+/// # 引数
+/// * `main_panel_layout` - 保存するパネルレイアウト（省略時は保存済みの値を維持）
+///
+/// # 戻り値
+/// * 保存したウィンドウ状態
+#[tauri::command]
+pub fn save_window_state(app: AppHandle, window: Window, main_panel_layout: Option<MainPanelLayout>) -> Result<WindowState, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::persist_window_state(&app, &config_dir, &window, main_panel_layout).map_err(|e| format!("ウィンドウ状態の保存に失敗しました: {}", e))
+}
+
+/// 設定全体をポータブルな JSON ファイルへ書き出すコマンド
+///
+/// # 引数
+/// * `path` - 書き出し先のファイルパス
+#[tauri::command]
+pub fn export_settings(app: AppHandle, path: String) -> Result<(), String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::export_settings(&app, &config_dir, Path::new(&path)).map_err(|e| format!("設定のエクスポートに失敗しました: {}", e))
+}
+
+/// 書き出した設定ファイルを検証して読み込むコマンド
+/// 検証に失敗した場合、現在の設定は変更しない
+///
+/// # 引数
+/// * `path` - 読み込むファイルパス
+///
+/// # 戻り値
+/// * 読み込んだキーの一覧
+#[tauri::command]
+pub fn import_settings(app: AppHandle, path: String) -> Result<Vec<String>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::import_settings(&app, &config_dir, Path::new(&path)).map_err(|e| format!("設定のインポートに失敗しました: {}", e))
+}
+
+/// 複数の設定キーをまとめて更新するコマンド
+/// すべての更新を適用してから一度だけ保存する
+///
+/// # 引数
+/// * `updates` - キーと新しい値の組（既知のキーは型を検証する）
+///
+/// # 戻り値
+/// * 更新したキーの一覧
+#[tauri::command]
+pub fn set_many(app: AppHandle, updates: serde_json::Map<String, serde_json::Value>) -> Result<Vec<String>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::set_many(&app, &config_dir, updates).map_err(|e| format!("設定の一括更新に失敗しました: {}", e))
+}
+
+/// キーボードショートカットの一覧を取得するコマンド
+///
+/// # 戻り値
+/// * 操作 ID・有効範囲・キーの組み合わせの一覧
+#[tauri::command]
+pub fn get_keybindings(app: AppHandle) -> Result<Vec<Keybinding>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::load_keybindings(&app, &config_dir).map_err(|e| format!("ショートカットの読み込みに失敗しました: {}", e))
+}
+
+/// キーボードショートカットを変更するコマンド
+/// 同じ範囲で既に使われているキーは割り当てられない
+///
+/// # 引数
+/// * `action` - 操作 ID（例: "flow_editor.save"）
+/// * `shortcut` - キーの組み合わせ（例: "Ctrl+Shift+S"、空文字で割り当て解除）
+///
+/// # 戻り値
+/// * 変更後のショートカット一覧
+#[tauri::command]
+pub fn set_keybinding(app: AppHandle, action: String, shortcut: String) -> Result<Vec<Keybinding>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::set_keybinding(&app, &config_dir, &action, &shortcut).map_err(|e| format!("ショートカットの変更に失敗しました: {}", e))
+}
+
+/// キーボードショートカットを初期値に戻すコマンド
+///
+/// # 引数
+/// * `action` - 初期値に戻す操作 ID（省略時はすべて）
+///
+/// # 戻り値
+/// * 変更後のショートカット一覧
+#[tauri::command]
+pub fn reset_keybindings(app: AppHandle, action: Option<String>) -> Result<Vec<Keybinding>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::reset_keybindings(&app, &config_dir, action.as_deref()).map_err(|e| format!("ショートカットのリセットに失敗しました: {}", e))
+}
+
+/// カスタムテーマの一覧を取得するコマンド
+#[tauri::command]
+pub fn list_custom_themes(app: AppHandle) -> Result<Vec<CustomTheme>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::load_custom_themes(&app, &config_dir).map_err(|e| format!("カスタムテーマの読み込みに失敗しました: {}", e))
+}
+
+/// カスタムテーマを作成するコマンド
+///
+/// # 引数
+/// * `theme` - テーマ名と色定義（色は `#RRGGBB` や `oklch()` などの CSS 表記）
+///
+/// # 戻り値
+/// * 作成後のカスタムテーマ一覧
+#[tauri::command]
+pub fn create_custom_theme(app: AppHandle, theme: CustomTheme) -> Result<Vec<CustomTheme>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::create_custom_theme(&app, &config_dir, theme).map_err(|e| format!("カスタムテーマの作成に失敗しました: {}", e))
+}
+
+/// カスタムテーマを更新するコマンド
+///
+/// # 引数
+/// * `name` - 更新するテーマの現在の名前
+/// * `theme` - 新しいテーマ名と色定義
+///
+/// # 戻り値
+/// * 更新後のカスタムテーマ一覧
+#[tauri::command]
+pub fn update_custom_theme(app: AppHandle, name: String, theme: CustomTheme) -> Result<Vec<CustomTheme>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::update_custom_theme(&app, &config_dir, &name, theme).map_err(|e| format!("カスタムテーマの更新に失敗しました: {}", e))
+}
+
+/// カスタムテーマを削除するコマンド
+/// 使用中のテーマを削除した場合、テーマは auto に戻る
+///
+/// # 引数
+/// * `name` - 削除するテーマ名
+///
+/// # 戻り値
+/// * 削除後のカスタムテーマ一覧
+#[tauri::command]
+pub fn delete_custom_theme(app: AppHandle, name: String) -> Result<Vec<CustomTheme>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::delete_custom_theme(&app, &config_dir, &name).map_err(|e| format!("カスタムテーマの削除に失敗しました: {}", e))
+}
+
+/// 指定した範囲の設定をデフォルト値に戻すコマンド
+/// 完了後に `settings-reset` イベントを送信する
+///
+/// # 引数
+/// * `scope` - リセット範囲（"window_state" / "window_config" / "project_config" / "all"）
+///
+/// # 戻り値
+/// * デフォルト値に戻したキーの一覧
+#[tauri::command]
+pub fn reset_settings(app: AppHandle, scope: ResetScope) -> Result<Vec<String>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::reset_settings(&app, &config_dir, scope).map_err(|e| format!("設定のリセットに失敗しました: {}", e))
+}
+
+/// 設定プロファイルの一覧を取得するコマンド
+///
+/// # 戻り値
+/// * 使用中のプロファイル名とすべてのプロファイル名
+#[tauri::command]
+pub fn list_profiles(app: AppHandle) -> Result<ProfileList, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::list_profiles(&app, &config_dir).map_err(|e| format!("設定プロファイルの取得に失敗しました: {}", e))
+}
+
+/// デフォルト設定で新しい設定プロファイルを作成するコマンド
+///
+/// # 引数
+/// * `name` - プロファイル名（英数字・-・_）
+///
+/// # 戻り値
+/// * 作成後のプロファイル一覧
+#[tauri::command]
+pub fn create_profile(app: AppHandle, name: String) -> Result<ProfileList, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::create_profile(&app, &config_dir, &name).map_err(|e| format!("設定プロファイルの作成に失敗しました: {}", e))
+}
+
+/// 設定プロファイルを複製するコマンド
+///
+/// # 引数
+/// * `source` - 複製元のプロファイル名
+/// * `name` - 新しいプロファイル名
+///
+/// # 戻り値
+/// * 複製後のプロファイル一覧
+#[tauri::command]
+pub fn duplicate_profile(app: AppHandle, source: String, name: String) -> Result<ProfileList, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::duplicate_profile(&app, &config_dir, &source, &name).map_err(|e| format!("設定プロファイルの複製に失敗しました: {}", e))
+}
+
+/// 使用中の設定プロファイルを切り替えるコマンド
+/// 切り替え後、新しいプロファイルのウィンドウ基本設定をメインウィンドウへ適用する
+///
+/// # 引数
+/// * `name` - 切り替え先のプロファイル名
+///
+/// # 戻り値
+/// * 切り替え後のプロファイル一覧
+#[tauri::command]
+pub fn switch_profile(app: AppHandle, name: String) -> Result<ProfileList, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  let list = store_manager::switch_profile(&app, &config_dir, &name).map_err(|e| format!("設定プロファイルの切り替えに失敗しました: {}", e))?;
+  if let Some(main_window) = app.get_webview_window("main") {
+    match store_manager::load_window_config(&app, &config_dir) {
+      Ok(window_config) => store_manager::apply_window_config(&main_window, &window_config),
+      Err(e) => error!("ウィンドウ設定の読み込みに失敗しました: {}", e),
+    }
+  }
+  Ok(list)
+}
+
+/// 設定の同期設定を取得するコマンド
+///
+/// # 戻り値
+/// * 同期の有効/無効と同期先フォルダ
+#[tauri::command]
+pub fn get_settings_sync(app: AppHandle) -> Result<SettingsSync, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::load_settings_sync(&app, &config_dir).map_err(|e| format!("同期設定の取得に失敗しました: {}", e))
+}
+
+/// 設定の同期設定を保存するコマンド
+///
+/// # 引数
+/// * `sync` - 同期の有効/無効と同期先フォルダ（Dropbox / OneDrive のフォルダなど）
+#[tauri::command]
+pub fn set_settings_sync(app: AppHandle, sync: SettingsSync) -> Result<(), String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::save_settings_sync(&app, &config_dir, &sync).map_err(|e| format!("同期設定の保存に失敗しました: {}", e))
+}
+
+/// 設定を同期先フォルダとすぐに同期するコマンド
+/// 両方が変更されていた場合は競合として返すので、`prefer` を指定して再実行する
+///
+/// # 引数
+/// * `prefer` - 競合時に優先する側（"local" / "remote"、省略時は競合を返す）
+///
+/// # 戻り値
+/// * 同期結果（"up_to_date" / "pushed" / "pulled" / "conflict"）
+#[tauri::command]
+pub fn sync_now(app: AppHandle, prefer: Option<SyncPreference>) -> Result<SyncOutcome, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::sync_settings(&app, &config_dir, prefer).map_err(|e| format!("設定の同期に失敗しました: {}", e))
+}
+
+/// プロジェクトに付いているタグの一覧を取得するコマンド
+///
+/// # 戻り値
+/// * タグ名と使用しているプロジェクト数（タグ名順）
+#[tauri::command]
+pub fn list_tags(app: AppHandle) -> Result<Vec<TagSummary>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::list_tags(&app, &config_dir).map_err(|e| format!("タグ一覧の取得に失敗しました: {}", e))
+}
+
+/// プロジェクトにタグを付けるコマンド
+///
+/// # 引数
+/// * `name` - プロジェクト名
+/// * `tag` - 付けるタグ
+///
+/// # 戻り値
+/// * 更新後のプロジェクト一覧
+#[tauri::command]
+pub fn add_project_tag(app: AppHandle, name: String, tag: String) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::add_project_tag(&app, &config_dir, &name, &tag).map_err(|e| format!("タグの追加に失敗しました: {}", e))
+}
+
+/// プロジェクトからタグを外すコマンド
+///
+/// # 引数
+/// * `name` - プロジェクト名
+/// * `tag` - 外すタグ
+///
+/// # 戻り値
+/// * 更新後のプロジェクト一覧
+#[tauri::command]
+pub fn remove_project_tag(app: AppHandle, name: String, tag: String) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::remove_project_tag(&app, &config_dir, &name, &tag).map_err(|e| format!("タグの削除に失敗しました: {}", e))
+}
+
+/// タグ名を全プロジェクトで変更するコマンド
+///
+/// # 引数
+/// * `from` - 変更前のタグ
+/// * `to` - 変更後のタグ
+///
+/// # 戻り値
+/// * 更新後のプロジェクト一覧
+#[tauri::command]
+pub fn rename_tag(app: AppHandle, from: String, to: String) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::rename_tag(&app, &config_dir, &from, &to).map_err(|e| format!("タグ名の変更に失敗しました: {}", e))
+}
+
+/// タグを全プロジェクトから削除するコマンド
+///
+/// # 引数
+/// * `tag` - 削除するタグ
+///
+/// # 戻り値
+/// * 更新後のプロジェクト一覧
+#[tauri::command]
+pub fn delete_tag(app: AppHandle, tag: String) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::delete_tag(&app, &config_dir, &tag).map_err(|e| format!("タグの削除に失敗しました: {}", e))
+}
+
+/// 登録済みプロジェクトを検索するコマンド
+///
+/// # 引数
+/// * `query` - 名前・パス・備考・タグに対する部分一致の検索語
+/// * `filters` - 絞り込み条件（必須タグ）
+///
+/// # 戻り値
+/// * 一致したプロジェクトと一致したフィールド
+#[tauri::command]
+pub fn search_workspace(app: AppHandle, query: String, filters: Option<SearchFilters>) -> Result<Vec<SearchResult>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::search_workspace(&app, &config_dir, &query, &filters.unwrap_or_default()).map_err(|e| format!("検索に失敗しました: {}", e))
+}
+
+/// プロジェクト別設定を取得するコマンド
+/// 設定ファイルが無い場合はデフォルト値で作成する
+/// 登録済みプロジェクトのフォルダ以外は読み書きしない
+///
+/// # 引数
+/// * `filepath` - プロジェクトフォルダのパス
+///
+/// # 戻り値
+/// * プロジェクト別設定
+#[tauri::command]
+pub fn get_project_settings(app: AppHandle, filepath: String) -> Result<ProjectSettings, String> {
+  let project_dir = safe_opener::resolve_project_root(&app, &filepath).map_err(|e| format!("プロジェクト別設定の読み込みに失敗しました: {}", e))?;
+  store_manager::load_project_settings(&project_dir).map_err(|e| format!("プロジェクト別設定の読み込みに失敗しました: {}", e))
+}
+
+/// プロジェクト別設定を保存するコマンド
+/// 登録済みプロジェクトのフォルダ以外には書き込まない
+///
+/// # 引数
+/// * `filepath` - プロジェクトフォルダのパス
+/// * `settings` - 保存するプロジェクト別設定
+#[tauri::command]
+pub fn save_project_settings(app: AppHandle, filepath: String, settings: ProjectSettings) -> Result<(), String> {
+  let project_dir = safe_opener::resolve_project_root(&app, &filepath).map_err(|e| format!("プロジェクト別設定の保存に失敗しました: {}", e))?;
+  store_manager::save_project_settings(&project_dir, &settings).map_err(|e| format!("プロジェクト別設定の保存に失敗しました: {}", e))
+}
+
+/// シークレット（データベースのパスワード、API トークンなど）を暗号化して保存するコマンド
+///
+/// # 引数
+/// * `name` - シークレット名
+/// * `value` - 保存する値
+#[tauri::command]
+pub fn set_secret(app: AppHandle, name: String, value: String) -> Result<(), String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::set_secret(&app, &config_dir, &name, &value).map_err(|e| format!("シークレットの保存に失敗しました: {}", e))
+}
+
+/// シークレットを復号して取得するコマンド
+///
+/// # 引数
+/// * `name` - シークレット名
+///
+/// # 戻り値
+/// * 復号した値（未登録なら None）
+#[tauri::command]
+pub fn get_secret(app: AppHandle, name: String) -> Result<Option<String>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::get_secret(&app, &config_dir, &name).map_err(|e| format!("シークレットの取得に失敗しました: {}", e))
+}
+
+/// シークレットを削除するコマンド
+///
+/// # 引数
+/// * `name` - シークレット名
+#[tauri::command]
+pub fn delete_secret(app: AppHandle, name: String) -> Result<(), String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::delete_secret(&app, &config_dir, &name).map_err(|e| format!("シークレットの削除に失敗しました: {}", e))
+}
+
+/// 登録済みシークレットの名前一覧を取得するコマンド（値は含まない）
+#[tauri::command]
+pub fn list_secrets(app: AppHandle) -> Result<Vec<String>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::list_secret_names(&app, &config_dir).map_err(|e| format!("シークレット一覧の取得に失敗しました: {}", e))
+}
+
+/// プロジェクトの説明・タグを更新するコマンド
+///
+/// # 引数
+/// * `name` - プロジェクト名
+/// * `description` - 新しい説明（省略時は変更しない）
+/// * `tags` - 新しいタグ一覧（省略時は変更しない、重複・空文字は除去）
+///
+/// # 戻り値
+/// * 更新後のプロジェクト
+#[tauri::command]
+pub fn update_project_metadata(app: AppHandle, name: String, description: Option<String>, tags: Option<Vec<String>>) -> Result<ProjectConfig, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::update_project_metadata(&app, &config_dir, &name, description, tags).map_err(|e| format!("プロジェクトのメタデータ更新に失敗しました: {}", e))
+}
+
+/// ワークスペース（開いているプロジェクトとアクティブなプロジェクト）を取得するコマンド
+#[tauri::command]
+pub fn get_workspace(app: AppHandle) -> Result<Workspace, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::load_workspace(&app, &config_dir).map_err(|e| format!("ワークスペースの取得に失敗しました: {}", e))
+}
+
+/// プロジェクトをワークスペースで開き、アクティブにするコマンド
+///
+/// # 引数
+/// * `name` - 開くプロジェクト名（プロジェクト一覧に登録済みであること）
+///
+/// # 戻り値
+/// * 更新後のワークスペース
+#[tauri::command]
+pub fn open_project_in_workspace(app: AppHandle, name: String) -> Result<Workspace, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::open_project_in_workspace(&app, &config_dir, &name).map_err(|e| format!("プロジェクトを開けませんでした: {}", e))
+}
+
+/// ワークスペースからプロジェクトを閉じるコマンド
+///
+/// # 引数
+/// * `name` - 閉じるプロジェクト名
+///
+/// # 戻り値
+/// * 更新後のワークスペース
+#[tauri::command]
+pub fn close_project(app: AppHandle, name: String) -> Result<Workspace, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::close_project(&app, &config_dir, &name).map_err(|e| format!("プロジェクトを閉じられませんでした: {}", e))
+}
+
+/// アクティブなプロジェクトを切り替えるコマンド
+///
+/// # 引数
+/// * `name` - アクティブにするプロジェクト名（ワークスペースで開いていること）
+///
+/// # 戻り値
+/// * 更新後のワークスペース
+#[tauri::command]
+pub fn switch_active_project(app: AppHandle, name: String) -> Result<Workspace, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::switch_active_project(&app, &config_dir, &name).map_err(|e| format!("プロジェクトの切り替えに失敗しました: {}", e))
+}
+
+/// ファイルダイアログの既定フォルダ設定を取得するコマンド
+#[tauri::command]
+pub fn get_dialog_directories(app: AppHandle) -> Result<DialogDirectories, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::load_dialog_directories(&app, &config_dir).map_err(|e| format!("既定フォルダ設定の取得に失敗しました: {}", e))
+}
+
+/// ファイルダイアログの既定フォルダ設定を保存するコマンド
+///
+/// # 引数
+/// * `directories` - 種別ごとの既定フォルダ、最後に使ったフォルダを記憶するかどうか
+#[tauri::command]
+pub fn set_dialog_directories(app: AppHandle, directories: DialogDirectories) -> Result<(), String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::save_dialog_directories(&app, &config_dir, &directories).map_err(|e| format!("既定フォルダ設定の保存に失敗しました: {}", e))
+}
+
+/// ファイルダイアログの種類
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum PickMode {
+  Open,   // ファイルを開く
+  Save,   // 保存先を指定
+  Folder, // フォルダを選択
+}
+
+/// 既定フォルダを反映したファイルダイアログを表示するコマンド
+/// 選択されたパスは「最後に使ったフォルダ」として記録する
+///
+/// # 引数
+/// * `category` - ダイアログの種別（"import" / "export" / "project"）
+/// * `mode` - ダイアログの種類（"open" / "save" / "folder"）
+/// * `project_dir` - プロジェクト別の既定フォルダを参照するプロジェクトフォルダ（省略可）
+///
+/// # 戻り値
+/// * 選択されたパス（キャンセル時は None）
+#[tauri::command]
+pub async fn pick_path(app: AppHandle, category: DialogCategory, mode: PickMode, project_dir: Option<String>) -> Result<Option<String>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  let start_dir = store_manager::resolve_dialog_directory(&app, &config_dir, category, project_dir.as_deref().map(Path::new)).map_err(|e| format!("既定フォルダの取得に失敗しました: {}", e))?;
+
+  let mut dialog = app.dialog().file();
+  if let Some(dir) = start_dir {
+    dialog = dialog.set_directory(dir);
+  }
+  // ブロッキング API を使うため、非同期コマンド（メインスレッド外）で呼び出す
+  let selected = match mode {
+    PickMode::Open => dialog.blocking_pick_file(),
+    PickMode::Save => dialog.blocking_save_file(),
+    PickMode::Folder => dialog.blocking_pick_folder(),
+  };
+  let selected = match selected {
+    Some(path) => path.into_path().map_err(|e| format!("選択されたパスを解釈できません: {}", e))?,
+    None => return Ok(None),
+  };
+
+  if let Err(e) = store_manager::remember_dialog_directory(&app, &config_dir, category, &selected) {
+    error!("最後に使ったフォルダの記録に失敗しました: {}", e);
+  }
+  Ok(Some(selected.to_string_lossy().to_string()))
+}
+
+/// プロジェクトをピン留めするコマンド
+///
+/// # 引数
+/// * `name` - プロジェクト名
+///
+/// # 戻り値
+/// * 更新後のプロジェクト一覧
+#[tauri::command]
+pub fn pin_project(app: AppHandle, name: String) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::set_project_pinned(&app, &config_dir, &name, true).map_err(|e| format!("ピン留めに失敗しました: {}", e))
+}
+
+/// プロジェクトのピン留めを解除するコマンド
+///
+/// # 引数
+/// * `name` - プロジェクト名
+///
+/// # 戻り値
+/// * 更新後のプロジェクト一覧
+#[tauri::command]
+pub fn unpin_project(app: AppHandle, name: String) -> Result<Vec<ProjectConfig>, String> {
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| e.to_string())?;
+  store_manager::set_project_pinned(&app, &config_dir, &name, false).map_err(|e| format!("ピン留めの解除に失敗しました: {}", e))
+}
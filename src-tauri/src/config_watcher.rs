@@ -0,0 +1,82 @@
+//! 設定ファイル監視モジュール
+//! `D4CleaningStudio.config` が外部で編集された時にストアを再読み込みし、
+//! フロントエンドへ `config-changed` イベントを通知する
+
+use std::{
+  ffi::OsStr,
+  path::{Path, PathBuf},
+  sync::{mpsc, Mutex},
+  time::Duration,
+};
+
+use log::{error, info};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::store_manager;
+
+/// 連続したファイル変更をまとめるための待ち時間
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// `config-changed` イベントのペイロード
+#[derive(Serialize, Clone, Debug)]
+pub struct ConfigChangedPayload {
+  pub path: String, // 変更された設定ファイルのパス
+}
+
+/// 設定ファイルの監視を開始する
+/// アトミックな置き換え（リネーム）でも検知できるよう、ファイルではなく設定ディレクトリを監視する
+/// ウォッチャーはアプリの管理状態として保持し、アプリ終了まで監視を続ける
+pub fn start_config_watcher(app: &AppHandle, config_dir: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+  let (tx, rx) = mpsc::channel::<()>();
+
+  let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+    Ok(event) => {
+      let is_config = event.paths.iter().any(|p| p.file_name() == Some(OsStr::new(store_manager::CONFIG_FILE_NAME)));
+      if is_config && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        let _ = tx.send(());
+      }
+    },
+    Err(e) => error!("設定ファイルの監視でエラーが発生しました: {}", e),
+  })?;
+  watcher.watch(&config_dir, RecursiveMode::NonRecursive)?;
+  app.manage(Mutex::new(watcher));
+
+  let app_handle = app.clone();
+  let path = config_dir.join(store_manager::CONFIG_FILE_NAME);
+  std::thread::spawn(move || {
+    while rx.recv().is_ok() {
+      // 書き込み中の連続イベントが落ち着くまで待つ
+      while rx.recv_timeout(DEBOUNCE).is_ok() {}
+      handle_config_change(&app_handle, &path);
+    }
+  });
+
+  info!("設定ファイルの監視を開始しました: {}", config_dir.display());
+  Ok(())
+}
+
+/// 設定ファイルの変更を処理
+/// アプリ自身の書き込みや、書き込み途中の不完全な JSON は無視する
+fn handle_config_change(app: &AppHandle, path: &Path) {
+  let Some(config_dir) = path.parent() else {
+    return;
+  };
+  match store_manager::reload_external_change(app, config_dir) {
+    Ok(true) => {},
+    Ok(false) => return,
+    Err(e) => {
+      error!("設定ファイルの再読み込みに失敗しました: {}", e);
+      return;
+    },
+  }
+
+  info!("設定ファイルが外部で変更されました: {}", path.display());
+  let payload = ConfigChangedPayload {
+    path: path.to_string_lossy().to_string(),
+  };
+  if let Err(e) = app.emit("config-changed", payload) {
+    error!("config-changed イベントの送信に失敗しました: {}", e);
+  }
+}
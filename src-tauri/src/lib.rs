@@ -4,7 +4,6 @@
 // 依存関係のインポート
 // ========================================================================================
 use chrono::Local; // 日時処理用（ログフォーマットで使用）
-use dirs_2; // ディレクトリパス取得用（設定ファイル保存場所の特定）
 use log::{error, info, LevelFilter}; // ロギング機能（デバッグ・エラー情報出力）
 use tauri::Manager; // Tauriアプリケーション管理機能
 use tauri_plugin_log::{Target, TargetKind}; // Tauriログプラグイン
@@ -20,6 +19,34 @@ mod store_manager;
 /// CPU・メモリ使用率の監視とバックグラウンド更新を担当
 mod system_monitor;
 
+/// システムロケール検出モジュール
+/// OS の言語・地域書式（小数点記号、週の始まり）の取得を担当
+mod system_locale;
+
+/// サンプルデータ生成モジュール
+/// デモ・ベンチマーク・テスト用の汚れたデータを生成
+mod sample_data;
+
+/// 設定ファイル監視モジュール
+/// 設定ファイルの外部変更を検知し、フロントエンドへ通知
+mod config_watcher;
+
+/// パスユーティリティモジュール
+/// UNC・長いパス・区切り文字の正規化など、ファイル操作前のパス処理を担当
+mod path_utils;
+
+/// 書き込み可否チェックモジュール
+/// エクスポート・保存前の書き込み先検証とクラウド同期フォルダの検出を担当
+mod write_access;
+
+/// 安全なファイルオープンモジュール
+/// 既知のフォルダ内に限定した「既定のアプリで開く」「フォルダで表示」を担当
+mod safe_opener;
+
+/// アクセシビリティ設定検出モジュール
+/// OS の「アニメーションを減らす」「ハイコントラスト」設定の取得と変更通知を担当
+mod accessibility;
+
 /// コマンドハンドラー モジュール
 /// フロントエンドから呼び出し可能なTauriコマンドを定義
 mod commands;
@@ -55,13 +82,9 @@ pub fn run() {
           Target::new(TargetKind::Webview), // Webview（ブラウザコンソール）
           Target::new(TargetKind::Folder {
             // ファイル出力
-            // ログファイル保存先: ユーザー設定ディレクトリ/D4CleaningStudio/
-            path: std::path::PathBuf::from(
-              dirs_2::config_dir()
-                .expect("Failed to get config dir") // 設定ディレクトリ取得失敗時はパニック
-                .join("D4CleaningStudio"), // アプリ専用サブディレクトリ
-            ),
-            file_name: Some("D4CleaningStudio".to_string()), // ログファイル名
+            // ログファイル保存先: 設定ディレクトリ（既定: ユーザー設定ディレクトリ/D4CleaningStudio/、ポータブルモードでは 実行ファイルのフォルダ/data/）
+            path: store_manager::resolve_config_dir().expect("Failed to get config dir"), // 設定ディレクトリ取得失敗時はパニック
+            file_name: Some("D4CleaningStudio".to_string()),                              // ログファイル名
           }),
         ])
         .max_file_size(4_000_000) // ログファイル最大サイズ: 4MB
@@ -88,10 +111,98 @@ pub fn run() {
     // ========================================================================================
     // JavaScript側から呼び出し可能なRust関数を登録
     .invoke_handler(tauri::generate_handler![
-        commands::greet, 
-        system_monitor::get_system_info
+      commands::greet,
+      commands::get_config_dir,
+      commands::is_portable_mode,
+      commands::get_performance_mode,
+      commands::set_performance_mode,
+      commands::list_layout_presets,
+      commands::save_layout_preset,
+      commands::apply_layout_preset,
+      commands::delete_layout_preset,
+      commands::get_column_preferences,
+      commands::save_column_preferences,
+      commands::list_projects,
+      commands::add_project,
+      commands::update_project,
+      commands::remove_project,
+      commands::update_project_metadata,
+      commands::pin_project,
+      commands::unpin_project,
+      commands::list_recent_projects,
+      commands::push_recent_project,
+      commands::open_recent_project,
+      commands::clear_recent_projects,
+      commands::get_workspace,
+      commands::open_project_in_workspace,
+      commands::close_project,
+      commands::switch_active_project,
+      commands::save_window_state,
+      commands::export_settings,
+      commands::import_settings,
+      commands::set_many,
+      commands::reset_settings,
+      commands::list_profiles,
+      commands::create_profile,
+      commands::duplicate_profile,
+      commands::switch_profile,
+      commands::get_settings_sync,
+      commands::set_settings_sync,
+      commands::sync_now,
+      commands::list_tags,
+      commands::add_project_tag,
+      commands::remove_project_tag,
+      commands::rename_tag,
+      commands::delete_tag,
+      commands::search_workspace,
+      commands::get_project_settings,
+      commands::save_project_settings,
+      commands::set_secret,
+      commands::get_secret,
+      commands::delete_secret,
+      commands::list_secrets,
+      commands::get_dialog_directories,
+      commands::set_dialog_directories,
+      commands::pick_path,
+      commands::get_keybindings,
+      commands::set_keybinding,
+      commands::reset_keybindings,
+      commands::list_custom_themes,
+      commands::create_custom_theme,
+      commands::update_custom_theme,
+      commands::delete_custom_theme,
+      system_monitor::get_system_info,
+      system_locale::get_system_locale,
+      sample_data::generate_sample_data,
+      write_access::check_write_access,
+      safe_opener::open_path,
+      safe_opener::reveal_path,
+      accessibility::get_accessibility_preferences
     ])
     // ========================================================================================
+    // ウィンドウイベント処理
+    // ========================================================================================
+    // メインウィンドウの移動・リサイズを記録し、閉じる時に状態を保存
+    .on_window_event(|window, event| {
+      if window.label() != "main" {
+        return;
+      }
+      match event {
+        tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+          store_manager::track_window_bounds(window);
+        },
+        tauri::WindowEvent::CloseRequested { .. } => match store_manager::resolve_config_dir() {
+          Ok(config_dir) => {
+            if let Err(e) = store_manager::persist_window_state(window.app_handle(), &config_dir, window, None) {
+              error!("ウィンドウ状態の保存に失敗しました: {}", e);
+            }
+          },
+          Err(e) => error!("{}", e),
+        },
+        _ => {},
+      }
+    })
+    // ========================================================================================
     // アプリケーション初期化処理
     // ========================================================================================
     .setup(|app| {
@@ -100,19 +211,25 @@ pub fn run() {
       // システム監視を開始
       let _app_handle = app.handle().clone();
       tauri::async_runtime::spawn(async move {
-          system_monitor::start_system_monitoring().await;
+        system_monitor::start_system_monitoring().await;
       });
 
+      // アクセシビリティ設定の監視を開始
+      accessibility::start_accessibility_watch(app.handle().clone());
+
       // ----------------------------------------------------------------------------------------
       // 設定ディレクトリの取得・準備
       // ----------------------------------------------------------------------------------------
-      let config_dir = match dirs_2::config_dir() {
-        Some(dir) => dir.join("D4CleaningStudio"), // %APPDATA%/D4CleaningStudio (Windows)
-        None => {
-          error!("設定ディレクトリの取得に失敗しました");
+      let config_dir = match store_manager::resolve_config_dir() {
+        Ok(dir) => dir, // %APPDATA%/D4CleaningStudio (Windows)、ポータブルモードでは実行ファイルの隣の data/
+        Err(e) => {
+          error!("{}", e);
           return Ok(()); // エラーでも続行（機能制限モード）
         },
       };
+      if store_manager::is_portable() {
+        info!("ポータブルモードで起動しました: {}", config_dir.display());
+      }
 
       // ----------------------------------------------------------------------------------------
       // ストア（設定ファイル）の初期化
@@ -123,6 +240,31 @@ pub fn run() {
         return Ok(()); // エラーでも続行
       }
 
+      // ----------------------------------------------------------------------------------------
+      // 設定ファイルの監視開始
+      // ----------------------------------------------------------------------------------------
+      if let Err(e) = config_watcher::start_config_watcher(&app.handle(), config_dir.clone()) {
+        error!("設定ファイルの監視開始に失敗しました: {}", e);
+      }
+
+      // ----------------------------------------------------------------------------------------
+      // 設定の同期（有効な場合のみ、起動時に同期先フォルダと同期）
+      // ----------------------------------------------------------------------------------------
+      if store_manager::load_settings_sync(&app.handle(), &config_dir).is_ok_and(|sync| sync.enabled) {
+        match store_manager::sync_settings(&app.handle(), &config_dir, None) {
+          Ok(outcome) => info!("起動時の設定同期: {:?}", outcome),
+          Err(e) => error!("起動時の設定同期に失敗しました: {}", e),
+        }
+      }
+
+      // ----------------------------------------------------------------------------------------
+      // パフォーマンスモードの適用
+      // ----------------------------------------------------------------------------------------
+      match store_manager::load_performance_mode(&app.handle(), &config_dir) {
+        Ok(mode) => system_monitor::apply_performance_mode(mode),
+        Err(e) => error!("パフォーマンスモードの読み込みに失敗しました: {}", e),
+      }
+
       // ----------------------------------------------------------------------------------------
       // ウィンドウ設定の読み込み
       // ----------------------------------------------------------------------------------------
@@ -148,25 +290,8 @@ pub fn run() {
       // メインウィンドウの設定適用
       // ----------------------------------------------------------------------------------------
       if let Some(main_window) = app.get_webview_window("main") {
-        // ウィンドウタイトル設定
-        if let Err(e) = main_window.set_title(&window_config.title) {
-          error!("タイトルの設定に失敗しました: {}", e);
-        }
-
-        // ウィンドウサイズ制限設定
-        if let Err(e) = main_window.set_min_size(Some(tauri::Size::Physical(tauri::PhysicalSize {
-          width: window_config.min_width,
-          height: window_config.min_height,
-        }))) {
-          error!("最小サイズの設定に失敗しました: {}", e);
-        }
-
-        if let Err(e) = main_window.set_max_size(Some(tauri::Size::Physical(tauri::PhysicalSize {
-          width: window_config.max_width,
-          height: window_config.max_height,
-        }))) {
-          error!("最大サイズの設定に失敗しました: {}", e);
-        }
+        // ウィンドウタイトル・サイズ制限設定
+        store_manager::apply_window_config(&main_window, &window_config);
 
         // ウィンドウサイズ設定（前回終了時のサイズを復元）
         if let Err(e) = main_window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
@@ -192,7 +317,7 @@ pub fn run() {
           }
         }
 
-        // テーマ設定（Light/Dark/自動検出）
+        // テーマ設定（Light/Dark/自動検出/カスタムテーマ）
         let theme = match window_state.theme.as_str() {
           "Light" => Some(tauri::Theme::Light), // ライトテーマ固定
           "Dark" => Some(tauri::Theme::Dark),   // ダークテーマ固定
@@ -204,7 +329,16 @@ pub fn run() {
               _ => None, // 検出失敗時はシステムデフォルト
             }
           },
-          _ => None, // その他の場合はシステムデフォルト
+          name => {
+            // カスタムテーマは明暗の指定に合わせてウィンドウのテーマを決める
+            match store_manager::load_custom_themes(&app.handle(), &config_dir) {
+              Ok(themes) => themes.iter().find(|t| t.name == name).map(|t| if t.dark { tauri::Theme::Dark } else { tauri::Theme::Light }),
+              Err(e) => {
+                error!("カスタムテーマの読み込みに失敗しました: {}", e);
+                None // 読み込めない場合はシステムデフォルト
+              },
+            }
+          },
         };
 
         if let Err(e) = main_window.set_theme(theme) {
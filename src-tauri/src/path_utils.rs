@@ -0,0 +1,214 @@
+//! パス正規化ユーティリティ
+//! ファイル操作の前にパスを揃えるためのヘルパーをまとめたモジュール
+//! - 区切り文字の統一（`/` と `\` の混在）
+//! - `.` / `..` の解決
+//! - Windows の長いパス（260 文字超）への `\\?\` プレフィックス付与（UNC パスは `\\?\UNC\`）
+//! - 末尾が空白・ピリオドのファイル名（Windows では作成・アクセスできない）の検出（Windows のみ）
+
+use std::path::{Component, Path, PathBuf};
+
+/// Windows の従来の最大パス長（MAX_PATH）
+pub const WINDOWS_MAX_PATH: usize = 260;
+
+/// パスの問題（ファイル操作前の検証で返す）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathIssue {
+  Empty,                      // パスが空
+  TrailingSpaceOrDot(String), // 末尾が空白・ピリオドの名前（Windows で扱えない）
+}
+
+impl std::fmt::Display for PathIssue {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      PathIssue::Empty => write!(f, "パスが空です"),
+      PathIssue::TrailingSpaceOrDot(name) => write!(f, "'{}' は末尾が空白またはピリオドのため Windows では扱えません", name),
+    }
+  }
+}
+
+impl std::error::Error for PathIssue {}
+
+/// 区切り文字を統一し、`.` / `..` を解決したパスを返す
+/// ファイルシステムにはアクセスしないため、存在しないパスにも使える
+/// （シンボリックリンクは解決しない）
+pub fn normalize(path: &Path) -> PathBuf {
+  // Windows では `/` も区切りとして扱われるが、表示・比較のため `\` に揃える
+  let unified: PathBuf = if cfg!(windows) {
+    PathBuf::from(path.to_string_lossy().replace('/', "\\"))
+  } else {
+    path.to_path_buf()
+  };
+
+  let mut normalized = PathBuf::new();
+  for component in unified.components() {
+    match component {
+      Component::CurDir => {},
+      Component::ParentDir => {
+        // ルートより上には上がらない。相対パスの先頭の `..` は残す
+        if matches!(normalized.components().next_back(), Some(Component::Normal(_))) {
+          normalized.pop();
+        } else if !normalized.has_root() {
+          normalized.push("..");
+        }
+      },
+      other => normalized.push(other.as_os_str()),
+    }
+  }
+  normalized
+}
+
+/// 名前が Windows で扱えない形式（末尾が空白・ピリオド）でないか検証する
+/// SharePoint や OneDrive から同期されたフォルダで作られることがある
+/// macOS / Linux ではこれらの名前も正しく扱えるため、空のパスだけを拒否する
+pub fn validate(path: &Path) -> Result<(), PathIssue> {
+  if path.as_os_str().is_empty() {
+    return Err(PathIssue::Empty);
+  }
+  if !cfg!(windows) {
+    return Ok(());
+  }
+  for component in path.components() {
+    if let Component::Normal(name) = component {
+      let name = name.to_string_lossy();
+      if name != "." && name != ".." && (name.ends_with(' ') || name.ends_with('.')) {
+        return Err(PathIssue::TrailingSpaceOrDot(name.to_string()));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Windows の予約デバイス名（CON, PRN, AUX, NUL, COM1〜9, LPT1〜9）かどうか
+/// 大文字小文字を区別せず、拡張子が付いていても予約名として扱われる（`nul.json` なども作成できない）
+/// Windows 以外では予約名がないため常に false
+pub fn is_reserved_name(name: &str) -> bool {
+  if !cfg!(windows) {
+    return false;
+  }
+  let stem = name.split('.').next().unwrap_or(name).trim_end().to_ascii_uppercase();
+  match stem.as_str() {
+    "CON" | "PRN" | "AUX" | "NUL" => true,
+    _ => match stem.strip_prefix("COM").or_else(|| stem.strip_prefix("LPT")) {
+      Some(digit) => digit.len() == 1 && matches!(digit.as_bytes()[0], b'1'..=b'9'),
+      None => false,
+    },
+  }
+}
+
+/// ファイル操作に渡すパスを用意する
+/// Windows では正規化したうえで、長いパスに拡張長プレフィックスを付ける
+/// （`\\server\share\...` は `\\?\UNC\server\share\...` に変換）
+/// Windows の API も `..` をシンボリックリンクを解決せずに文字列上で取り除くため、事前に正規化しても開くファイルは変わらない
+/// （プレフィックス付きのパスは API が正規化しないため、事前の正規化が必要）
+/// Windows 以外では `..` はシンボリックリンクの解決後に辿られるため、パスを変更せずに OS へ渡す
+pub fn for_io(path: &Path) -> PathBuf {
+  if !cfg!(windows) {
+    return path.to_path_buf();
+  }
+  let normalized = normalize(path);
+  let text = normalized.to_string_lossy();
+  if text.len() >= WINDOWS_MAX_PATH && !text.starts_with(r"\\?\") && normalized.is_absolute() {
+    return match text.strip_prefix(r"\\") {
+      Some(unc) => PathBuf::from(format!(r"\\?\UNC\{}", unc)),
+      None => PathBuf::from(format!(r"\\?\{}", text)),
+    };
+  }
+  normalized
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn normalize_resolves_dot_segments() {
+    assert_eq!(normalize(Path::new("a/./b/../c")), PathBuf::from("a").join("c"));
+  }
+
+  #[test]
+  fn normalize_keeps_leading_parent_of_relative_path() {
+    assert_eq!(normalize(Path::new("../a/../../b")), PathBuf::from("..").join("..").join("b"));
+  }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn normalize_does_not_climb_above_root() {
+    assert_eq!(normalize(Path::new("/../a/../../b")), PathBuf::from("/b"));
+  }
+
+  #[cfg(windows)]
+  #[test]
+  fn normalize_unifies_separators_and_stops_at_root() {
+    assert_eq!(normalize(Path::new("C:/data/../../a/b")), PathBuf::from(r"C:\a\b"));
+  }
+
+  #[test]
+  fn validate_rejects_empty_path() {
+    assert_eq!(validate(Path::new("")), Err(PathIssue::Empty));
+  }
+
+  #[cfg(windows)]
+  #[test]
+  fn validate_rejects_trailing_space_or_dot() {
+    assert_eq!(validate(Path::new("dir/name.")), Err(PathIssue::TrailingSpaceOrDot(String::from("name."))));
+    assert_eq!(validate(Path::new("dir /file.csv")), Err(PathIssue::TrailingSpaceOrDot(String::from("dir "))));
+  }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn validate_accepts_trailing_space_or_dot_outside_windows() {
+    assert_eq!(validate(Path::new("dir/v1.")), Ok(()));
+    assert_eq!(validate(Path::new("dir /file.csv")), Ok(()));
+  }
+
+  #[test]
+  fn validate_accepts_dot_segments() {
+    assert_eq!(validate(Path::new("./a/../b.csv")), Ok(()));
+  }
+
+  #[test]
+  fn for_io_keeps_short_paths_normalized() {
+    assert_eq!(for_io(Path::new("a/./b")), PathBuf::from("a").join("b"));
+  }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn for_io_leaves_parent_segments_to_the_os() {
+    assert_eq!(for_io(Path::new("link/../b")).as_os_str(), "link/../b");
+  }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn for_io_does_not_prefix_long_paths_outside_windows() {
+    let long = format!("/{}", "a".repeat(WINDOWS_MAX_PATH));
+    assert_eq!(for_io(Path::new(&long)), PathBuf::from(&long));
+  }
+
+  #[cfg(windows)]
+  #[test]
+  fn for_io_prefixes_long_paths() {
+    let long = format!(r"C:\{}", "a".repeat(WINDOWS_MAX_PATH));
+    assert_eq!(for_io(Path::new(&long)), PathBuf::from(format!(r"\\?\{}", long)));
+
+    let unc = format!(r"\\server\share\{}", "a".repeat(WINDOWS_MAX_PATH));
+    assert_eq!(for_io(Path::new(&unc)), PathBuf::from(format!(r"\\?\UNC\server\share\{}", "a".repeat(WINDOWS_MAX_PATH))));
+  }
+
+  #[cfg(windows)]
+  #[test]
+  fn is_reserved_name_matches_device_names() {
+    for name in ["CON", "nul", "Aux.json", "com1", "LPT9"] {
+      assert!(is_reserved_name(name), "{}", name);
+    }
+    for name in ["CONSOLE", "COM0", "COM10", "default", "lpt"] {
+      assert!(!is_reserved_name(name), "{}", name);
+    }
+  }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn is_reserved_name_is_windows_only() {
+    assert!(!is_reserved_name("CON"));
+    assert!(!is_reserved_name("nul.json"));
+  }
+}
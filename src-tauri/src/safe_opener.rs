@@ -0,0 +1,223 @@
+//! 既定のアプリで開く・フォルダを表示する操作の安全なラッパー
+//! opener プラグインを直接呼ばず、対象が既知のフォルダ（登録済みプロジェクト・エクスポート先）の
+//! 中にあることを確認してから起動する
+//! - 既知のフォルダは書き込み時（`validate_root`）と読み込み時の両方で検証し、
+//!   ドライブのルート・ホームフォルダ・システムフォルダのような広すぎる範囲は許可しない
+//! - 操作の結果は設定ディレクトリの監査ログ（`audit.log`、JSON Lines）へ追記する
+
+use std::{
+  fs::OpenOptions,
+  io::Write,
+  path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+use log::{error, info, warn};
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::{
+  path_utils,
+  store_manager::{self, DialogCategory},
+  write_access,
+};
+
+/// 監査ログのファイル名（設定ディレクトリ内）
+pub const AUDIT_LOG_FILE_NAME: &str = "audit.log";
+
+/// ファイルを開く操作の失敗理由
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OpenPathError {
+  InvalidPath { message: String },      // パスの形式が正しくない
+  NotFound { path: String },            // 対象が存在しない
+  OutsideAllowedRoots { path: String }, // 既知のフォルダの外にある
+  RootTooBroad { path: String },        // 既知のフォルダとして登録するには範囲が広すぎる
+  UnknownProject { path: String },      // 登録済みのプロジェクトフォルダではない
+  Config { message: String },           // 許可するフォルダの一覧を取得できない
+  LaunchFailed { message: String },     // アプリ・ファイルマネージャーの起動に失敗
+}
+
+impl std::fmt::Display for OpenPathError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      OpenPathError::InvalidPath { message } => write!(f, "{}", message),
+      OpenPathError::NotFound { path } => write!(f, "ファイルが見つかりません: {}", path),
+      OpenPathError::OutsideAllowedRoots { path } => write!(f, "プロジェクトフォルダ・エクスポート先以外のファイルは開けません: {}", path),
+      OpenPathError::RootTooBroad { path } => write!(f, "ドライブのルート・ホームフォルダ・システムフォルダはプロジェクトフォルダ・エクスポート先に指定できません: {}", path),
+      OpenPathError::UnknownProject { path } => write!(f, "登録済みのプロジェクトフォルダではありません: {}", path),
+      OpenPathError::Config { message } => write!(f, "許可するフォルダの取得に失敗しました: {}", message),
+      OpenPathError::LaunchFailed { message } => write!(f, "起動に失敗しました: {}", message),
+    }
+  }
+}
+
+impl std::error::Error for OpenPathError {}
+
+/// 監査ログの1行
+#[derive(Serialize)]
+struct AuditEntry<'a> {
+  time: String,    // 日時（RFC 3339、UTC）
+  action: &'a str, // 操作（"open" / "reveal"）
+  path: &'a str,   // 要求されたパス
+  result: &'a str, // 結果（"allowed" / 失敗理由の kind）
+}
+
+/// 監査ログへ1行追記する
+/// 監査ログに書き込めなくても操作自体は止めず、アプリのログにエラーを残す
+fn audit(action: &str, path: &str, result: &str) {
+  let entry = AuditEntry {
+    time: Utc::now().to_rfc3339(),
+    action,
+    path,
+    result,
+  };
+  let written = store_manager::resolve_config_dir().and_then(|dir| {
+    let mut line = serde_json::to_vec(&entry)?;
+    line.push(b'\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(path_utils::for_io(&dir.join(AUDIT_LOG_FILE_NAME)))?;
+    file.write_all(&line)?;
+    Ok(())
+  });
+  if let Err(e) = written {
+    error!("監査ログへの書き込みに失敗しました: {}", e);
+  }
+}
+
+/// 開くことを許可するフォルダとして使えるか検証し、正規化したパスを返す
+/// ドライブのルート、ホームフォルダとその上位、システムフォルダとその配下は範囲が広すぎるため許可しない
+/// 存在しないフォルダは（作成前のプロジェクトフォルダなど）`.` / `..` を解決したパスで判定する
+fn resolve_root(root: &str) -> Result<PathBuf, OpenPathError> {
+  let path = Path::new(root);
+  path_utils::validate(path).map_err(|e| OpenPathError::InvalidPath { message: e.to_string() })?;
+  if !path.is_absolute() {
+    return Err(OpenPathError::InvalidPath {
+      message: format!("絶対パスを指定してください: {}", root),
+    });
+  }
+  let resolved = std::fs::canonicalize(path_utils::for_io(path)).unwrap_or_else(|_| path_utils::normalize(path));
+
+  let too_broad = resolved.parent().is_none()
+    || dirs_2::home_dir()
+      .map(|home| std::fs::canonicalize(&home).unwrap_or(home))
+      .is_some_and(|home| home.starts_with(&resolved))
+    || write_access::system_dirs()
+      .iter()
+      .any(|dir| resolved.starts_with(std::fs::canonicalize(dir).unwrap_or_else(|_| dir.clone())));
+  if too_broad {
+    return Err(OpenPathError::RootTooBroad { path: root.to_string() });
+  }
+  Ok(resolved)
+}
+
+/// プロジェクトフォルダ・エクスポート先として保存してよいか検証する（空文字は未設定として許可）
+/// 設定を書き込む前に呼び出し、フロントエンドから広すぎるフォルダを登録されないようにする
+pub fn validate_root(root: &str) -> Result<(), OpenPathError> {
+  if root.trim().is_empty() {
+    return Ok(());
+  }
+  resolve_root(root).map(|_| ())
+}
+
+/// 開くことを許可するフォルダの一覧（登録済みプロジェクトと、エクスポートの既定・最後に使ったフォルダ）
+/// 保存済みの値も検証し直し、広すぎるフォルダや存在しないフォルダは除外する
+fn allowed_roots(app: &AppHandle) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+  let config_dir = store_manager::resolve_config_dir()?;
+  let mut roots: Vec<String> = store_manager::load_projects(app, &config_dir)?.into_iter().map(|p| p.filepath).collect();
+  let dirs = store_manager::load_dialog_directories(app, &config_dir)?;
+  roots.extend(dirs.defaults.get(&DialogCategory::Export).cloned());
+  roots.extend(dirs.last_used.get(&DialogCategory::Export).cloned());
+
+  // シンボリックリンクや `..` で外へ出られないよう、実在するフォルダを正規化して比較する
+  let mut allowed = Vec::new();
+  for root in roots.iter().filter(|r| !r.trim().is_empty()) {
+    match resolve_root(root) {
+      Ok(resolved) if path_utils::for_io(&resolved).is_dir() => allowed.push(resolved),
+      Ok(_) => {},
+      Err(e) => warn!("許可するフォルダから除外しました: {}", e),
+    }
+  }
+  Ok(allowed)
+}
+
+/// 対象が許可されたフォルダの中にあることを確認し、正規化したパスを返す
+fn resolve_target(app: &AppHandle, path: &str) -> Result<PathBuf, OpenPathError> {
+  let requested = Path::new(path);
+  path_utils::validate(requested).map_err(|e| OpenPathError::InvalidPath { message: e.to_string() })?;
+  let target = std::fs::canonicalize(path_utils::for_io(requested)).map_err(|_| OpenPathError::NotFound { path: path.to_string() })?;
+
+  let roots = allowed_roots(app).map_err(|e| OpenPathError::Config { message: e.to_string() })?;
+  if !roots.iter().any(|root| target.starts_with(root)) {
+    return Err(OpenPathError::OutsideAllowedRoots { path: path.to_string() });
+  }
+  Ok(target)
+}
+
+/// 登録済みプロジェクトのフォルダそのものであることを確認し、正規化したパスを返す
+/// プロジェクト別設定のように、プロジェクトフォルダ直下のファイルを読み書きする前に呼び出す
+pub fn resolve_project_root(app: &AppHandle, path: &str) -> Result<PathBuf, OpenPathError> {
+  let requested = resolve_root(path)?;
+  let config_dir = store_manager::resolve_config_dir().map_err(|e| OpenPathError::Config { message: e.to_string() })?;
+  let projects = store_manager::load_projects(app, &config_dir).map_err(|e| OpenPathError::Config { message: e.to_string() })?;
+  if !projects.iter().filter_map(|p| resolve_root(&p.filepath).ok()).any(|root| root == requested) {
+    return Err(OpenPathError::UnknownProject { path: path.to_string() });
+  }
+  Ok(requested)
+}
+
+/// 失敗理由の種類（監査ログ用）
+fn error_kind(e: &OpenPathError) -> &'static str {
+  match e {
+    OpenPathError::InvalidPath { .. } => "invalid_path",
+    OpenPathError::NotFound { .. } => "not_found",
+    OpenPathError::OutsideAllowedRoots { .. } => "outside_allowed_roots",
+    OpenPathError::RootTooBroad { .. } => "root_too_broad",
+    OpenPathError::UnknownProject { .. } => "unknown_project",
+    OpenPathError::Config { .. } => "config",
+    OpenPathError::LaunchFailed { .. } => "launch_failed",
+  }
+}
+
+/// 許可されたフォルダ内か確認してから操作を実行し、結果を監査ログへ記録する
+fn run_audited(app: &AppHandle, action: &str, path: &str, launch: impl FnOnce(&Path) -> Result<(), OpenPathError>) -> Result<(), OpenPathError> {
+  let result = resolve_target(app, path).and_then(|target| launch(&target));
+  match &result {
+    Ok(()) => {
+      info!("{} を実行しました: {}", action, path);
+      audit(action, path, "allowed");
+    },
+    Err(e) => {
+      warn!("{} を実行しませんでした: {}", action, e);
+      audit(action, path, error_kind(e));
+    },
+  }
+  result
+}
+
+/// ファイルを既定のアプリで開くコマンド
+/// 登録済みプロジェクトフォルダ・エクスポート先の中にあるファイルだけを開く
+///
+/// # 引数
+/// * `path` - 開くファイルのパス
+#[tauri::command]
+pub fn open_path(app: AppHandle, path: String) -> Result<(), OpenPathError> {
+  run_audited(&app, "open", &path, |target| {
+    app
+      .opener()
+      .open_path(target.to_string_lossy(), None::<&str>)
+      .map_err(|e| OpenPathError::LaunchFailed { message: e.to_string() })
+  })
+}
+
+/// ファイルを含むフォルダをファイルマネージャーで表示するコマンド
+/// 登録済みプロジェクトフォルダ・エクスポート先の中にあるファイルだけを表示する
+///
+/// # 引数
+/// * `path` - 表示するファイル・フォルダのパス
+#[tauri::command]
+pub fn reveal_path(app: AppHandle, path: String) -> Result<(), OpenPathError> {
+  run_audited(&app, "reveal", &path, |target| {
+    app.opener().reveal_item_in_dir(target).map_err(|e| OpenPathError::LaunchFailed { message: e.to_string() })
+  })
+}
@@ -0,0 +1,384 @@
+//! デモ・ベンチマーク・結合テスト用のサンプルデータ生成モジュール
+//! - 現実的な「汚れた」データ（誤字、全角/半角の混在、文字コードの混在、重複、欠損）を生成
+//!   文字コードの混在は、UTF-8 のファイルに Shift_JIS の行が紛れ込んだ状態（別システムの出力を連結したものなど）を再現する
+//! - シードを指定すれば同じ内容を再生成できる
+
+use std::{fs::File, io::Write, path::PathBuf};
+
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use crate::{path_utils, write_access};
+
+/// サンプルデータ生成の指定
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SampleDataSpec {
+  pub path: String,        // 出力先 CSV ファイルのパス
+  pub rows: usize,         // 生成する行数（重複行を含む）
+  pub seed: u64,           // 乱数シード（同じシードなら同じ内容）
+  pub typo_rate: f64,      // 誤字を混入させる割合（0.0〜1.0）
+  pub width_mix_rate: f64, // 全角/半角を混在させる割合（0.0〜1.0）
+  pub sjis_rate: f64,      // Shift_JIS で出力する行の割合（0.0〜1.0、残りは UTF-8）
+  pub duplicate_rate: f64, // 重複行を混入させる割合（0.0〜1.0）
+  pub missing_rate: f64,   // 欠損値にする割合（0.0〜1.0）
+}
+
+impl Default for SampleDataSpec {
+  fn default() -> Self {
+    SampleDataSpec {
+      path: String::new(),
+      rows: 1000,
+      seed: 42,
+      typo_rate: 0.05,
+      width_mix_rate: 0.1,
+      sjis_rate: 0.05,
+      duplicate_rate: 0.05,
+      missing_rate: 0.05,
+    }
+  }
+}
+
+/// サンプルデータ生成結果の概要
+#[derive(Serialize, Clone, Debug)]
+pub struct SampleDataSummary {
+  pub path: String,      // 出力先
+  pub rows: usize,       // 出力行数（ヘッダー除く）
+  pub duplicates: usize, // 混入させた重複行数
+  pub typos: usize,      // 混入させた誤字の数
+  pub missing: usize,    // 欠損にしたセルの数
+  pub sjis_rows: usize,  // Shift_JIS で出力した行数
+}
+
+// 生成元の語彙
+const FAMILY_NAMES: &[&str] = &["佐藤", "鈴木", "高橋", "田中", "伊藤", "渡辺", "山本", "中村", "小林", "加藤"];
+const GIVEN_NAMES: &[&str] = &["太郎", "花子", "一郎", "美咲", "健太", "陽子", "翔太", "由美", "大輔", "恵"];
+const COMPANIES: &[&str] = &["株式会社サンプル", "有限会社テスト商事", "合同会社デモ工業", "株式会社見本物産", "一般社団法人例示協会"];
+const PREFECTURES: &[&str] = &["東京都", "大阪府", "愛知県", "福岡県", "北海道", "神奈川県"];
+const CITIES: &[&str] = &["中央区", "北区", "南区", "港区", "緑区", "西区"];
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.co.jp", "example.net"];
+
+// 誤字として使う似た文字の組み合わせ
+const TYPO_PAIRS: &[(char, char)] = &[('藤', '籐'), ('辺', '邊'), ('高', '髙'), ('崎', '﨑'), ('会', '絵'), ('株', '珠')];
+
+/// シード付き擬似乱数生成器（xorshift64*）
+/// 依存クレートを増やさず、シードから決定的な列を得るために使う
+struct SampleRng(u64);
+
+impl SampleRng {
+  fn new(seed: u64) -> Self {
+    // シード 0 では状態が変化しないため固定値に置き換える
+    SampleRng(if seed == 0 { 0x9e37_79b9_7f4a_7c15 } else { seed })
+  }
+
+  fn next_u64(&mut self) -> u64 {
+    self.0 ^= self.0 >> 12;
+    self.0 ^= self.0 << 25;
+    self.0 ^= self.0 >> 27;
+    self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+  }
+
+  /// 0.0 以上 1.0 未満の乱数
+  fn next_f64(&mut self) -> f64 {
+    (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  /// 確率 `rate` で true を返す
+  fn chance(&mut self, rate: f64) -> bool {
+    self.next_f64() < rate
+  }
+
+  fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+    items[(self.next_u64() % items.len() as u64) as usize]
+  }
+}
+
+/// 半角英数字を全角に変換する（全角/半角混在データの再現用）
+fn to_full_width(value: &str) -> String {
+  value
+    .chars()
+    .map(|c| match c {
+      '!'..='~' => char::from_u32(c as u32 + 0xfee0).unwrap_or(c),
+      ' ' => '　',
+      _ => c,
+    })
+    .collect()
+}
+
+/// 文字列中の一文字を似た文字に置き換える（誤字の再現用）
+fn inject_typo(value: &str, rng: &mut SampleRng) -> Option<String> {
+  let candidates: Vec<&(char, char)> = TYPO_PAIRS.iter().filter(|(from, _)| value.contains(*from)).collect();
+  if candidates.is_empty() {
+    return None;
+  }
+  let (from, to) = candidates[(rng.next_u64() % candidates.len() as u64) as usize];
+  Some(value.replacen(*from, &to.to_string(), 1))
+}
+
+/// CSV の1セルをエスケープ
+fn escape_csv(value: &str) -> String {
+  if value.contains(',') || value.contains('"') || value.contains('\n') {
+    format!("\"{}\"", value.replace('"', "\"\""))
+  } else {
+    value.to_string()
+  }
+}
+
+/// ノイズの割合がすべて 0.0〜1.0 の数値か検証する（NaN も拒否する）
+fn validate_rates(spec: &SampleDataSpec) -> Result<(), String> {
+  let rates = [
+    ("typo_rate", spec.typo_rate),
+    ("width_mix_rate", spec.width_mix_rate),
+    ("sjis_rate", spec.sjis_rate),
+    ("duplicate_rate", spec.duplicate_rate),
+    ("missing_rate", spec.missing_rate),
+  ];
+  match rates.iter().find(|(_, rate)| !(0.0..=1.0).contains(rate)) {
+    Some((name, rate)) => Err(format!("{} は 0.0〜1.0 の範囲で指定してください: {}", name, rate)),
+    None => Ok(()),
+  }
+}
+
+/// 仕様に従ってサンプルデータを生成し、CSV（UTF-8 BOM 付き、`sjis_rate` の割合の行は Shift_JIS）として書き出す
+pub fn write_sample_csv(spec: &SampleDataSpec) -> Result<SampleDataSummary, Box<dyn std::error::Error>> {
+  let mut rng = SampleRng::new(spec.seed);
+  let mut rows: Vec<Vec<String>> = Vec::with_capacity(spec.rows);
+  let mut duplicates = 0;
+  let mut typos = 0;
+  let mut missing = 0;
+
+  while rows.len() < spec.rows {
+    // 既存行の重複を混入
+    if !rows.is_empty() && rng.chance(spec.duplicate_rate) {
+      let source = rows[(rng.next_u64() % rows.len() as u64) as usize].clone();
+      rows.push(source);
+      duplicates += 1;
+      continue;
+    }
+
+    let id = rows.len() + 1;
+    let family = rng.pick(FAMILY_NAMES);
+    let given = rng.pick(GIVEN_NAMES);
+    let mut cells = vec![
+      format!("C{:06}", id),
+      format!("{} {}", family, given),
+      rng.pick(COMPANIES).to_string(),
+      format!(
+        "{}{}{}-{}-{}",
+        rng.pick(PREFECTURES),
+        rng.pick(CITIES),
+        rng.next_u64() % 9 + 1,
+        rng.next_u64() % 20 + 1,
+        rng.next_u64() % 30 + 1
+      ),
+      format!("0{}-{:04}-{:04}", rng.next_u64() % 9 + 1, rng.next_u64() % 10000, rng.next_u64() % 10000),
+      format!("user{}@{}", id, rng.pick(EMAIL_DOMAINS)),
+    ];
+
+    for (index, cell) in cells.iter_mut().enumerate() {
+      // ID 列は汚さない
+      if index == 0 {
+        continue;
+      }
+      if rng.chance(spec.missing_rate) {
+        cell.clear();
+        missing += 1;
+        continue;
+      }
+      if rng.chance(spec.typo_rate) {
+        if let Some(typo) = inject_typo(cell, &mut rng) {
+          *cell = typo;
+          typos += 1;
+        }
+      }
+      if rng.chance(spec.width_mix_rate) {
+        *cell = to_full_width(cell);
+      }
+    }
+    rows.push(cells);
+  }
+
+  let path = PathBuf::from(&spec.path);
+  write_access::check_writable(&path)?;
+  let mut file = File::create(path_utils::for_io(&path))?;
+  file.write_all("\u{feff}id,name,company,address,phone,email\n".as_bytes())?;
+  let mut sjis_rows = 0;
+  for row in &rows {
+    let cells: Vec<String> = row.iter().map(|cell| escape_csv(cell)).collect();
+    let line = format!("{}\n", cells.join(","));
+    if rng.chance(spec.sjis_rate) {
+      // Shift_JIS で表せない文字は数値文字参照になる（実際の変換時の文字化けに近い）
+      let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode(&line);
+      file.write_all(&bytes)?;
+      sjis_rows += 1;
+    } else {
+      file.write_all(line.as_bytes())?;
+    }
+  }
+
+  info!("サンプルデータを生成しました: {} ({}行)", path.display(), rows.len());
+  Ok(SampleDataSummary {
+    path: spec.path.clone(),
+    rows: rows.len(),
+    duplicates,
+    typos,
+    missing,
+    sjis_rows,
+  })
+}
+
+/// サンプルデータを生成するコマンド
+/// オンボーディング・ベンチマーク・結合テスト用
+///
+/// # 引数
+/// * `spec` - 出力先・行数・シード・各種ノイズの割合
+///
+/// # 戻り値
+/// * 生成結果の概要（行数、混入させた重複・誤字・欠損の数、Shift_JIS の行数）
+#[tauri::command]
+pub fn generate_sample_data(spec: SampleDataSpec) -> Result<SampleDataSummary, String> {
+  if spec.path.is_empty() {
+    return Err("出力先のパスが指定されていません".to_string());
+  }
+  validate_rates(&spec)?;
+  write_sample_csv(&spec).map_err(|e| format!("サンプルデータの生成に失敗しました: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// テスト用の出力先（一時フォルダ内、テストごとに別ファイル）
+  fn temp_csv(name: &str) -> String {
+    std::env::temp_dir().join(format!("d4cs-sample-{}-{}.csv", name, std::process::id())).to_string_lossy().into_owned()
+  }
+
+  /// ノイズをすべて 0 にした指定
+  fn clean_spec(name: &str) -> SampleDataSpec {
+    SampleDataSpec {
+      path: temp_csv(name),
+      rows: 10000,
+      seed: 7,
+      typo_rate: 0.0,
+      width_mix_rate: 0.0,
+      sjis_rate: 0.0,
+      duplicate_rate: 0.0,
+      missing_rate: 0.0,
+    }
+  }
+
+  /// 生成した CSV を読み込み、ヘッダーを除いた各行のセルを返す（テスト後にファイルは削除）
+  fn generate(spec: &SampleDataSpec) -> (SampleDataSummary, Vec<Vec<String>>) {
+    let summary = write_sample_csv(spec).unwrap();
+    let text = std::fs::read_to_string(&spec.path).unwrap();
+    std::fs::remove_file(&spec.path).unwrap();
+    let rows = text.lines().skip(1).map(|line| line.split(',').map(String::from).collect()).collect();
+    (summary, rows)
+  }
+
+  /// 生成した CSV をバイト列のまま読み込み、ヘッダーを除いた各行を返す（テスト後にファイルは削除）
+  fn generate_bytes(spec: &SampleDataSpec) -> (SampleDataSummary, Vec<Vec<u8>>) {
+    let summary = write_sample_csv(spec).unwrap();
+    let bytes = std::fs::read(&spec.path).unwrap();
+    std::fs::remove_file(&spec.path).unwrap();
+    let lines = bytes.split(|b| *b == b'\n').skip(1).filter(|line| !line.is_empty()).map(<[u8]>::to_vec).collect();
+    (summary, lines)
+  }
+
+  fn assert_rate(count: usize, total: usize, rate: f64) {
+    let actual = count as f64 / total as f64;
+    assert!((actual - rate).abs() < 0.02, "{} / {} = {} (期待値 {})", count, total, actual, rate);
+  }
+
+  #[test]
+  fn same_seed_produces_identical_output() {
+    let spec = SampleDataSpec {
+      path: temp_csv("seed"),
+      rows: 500,
+      ..SampleDataSpec::default()
+    };
+    write_sample_csv(&spec).unwrap();
+    let first = std::fs::read(&spec.path).unwrap();
+    write_sample_csv(&spec).unwrap();
+    let second = std::fs::read(&spec.path).unwrap();
+    write_sample_csv(&SampleDataSpec { seed: spec.seed + 1, ..spec.clone() }).unwrap();
+    let other = std::fs::read(&spec.path).unwrap();
+    std::fs::remove_file(&spec.path).unwrap();
+
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+  }
+
+  #[test]
+  fn duplicates_match_requested_rate() {
+    let spec = SampleDataSpec {
+      duplicate_rate: 0.2,
+      ..clean_spec("duplicates")
+    };
+    let (summary, rows) = generate(&spec);
+
+    let unique: std::collections::HashSet<&Vec<String>> = rows.iter().collect();
+    assert_eq!(rows.len(), spec.rows);
+    assert_eq!(summary.duplicates, rows.len() - unique.len());
+    assert_rate(summary.duplicates, spec.rows, spec.duplicate_rate);
+  }
+
+  #[test]
+  fn missing_cells_match_requested_rate() {
+    let spec = SampleDataSpec {
+      missing_rate: 0.1,
+      ..clean_spec("missing")
+    };
+    let (summary, rows) = generate(&spec);
+
+    // ID 列は欠損させない
+    let cells = rows.len() * 5;
+    let empty = rows.iter().flat_map(|row| row.iter().skip(1)).filter(|cell| cell.is_empty()).count();
+    assert_eq!(summary.missing, empty);
+    assert_rate(summary.missing, cells, spec.missing_rate);
+  }
+
+  #[test]
+  fn typos_match_requested_rate() {
+    let spec = SampleDataSpec {
+      typo_rate: 0.3,
+      ..clean_spec("typos")
+    };
+    let (summary, rows) = generate(&spec);
+
+    // 誤字にできるのは置き換え元の文字を含むセルだけ
+    let has_typo = |cell: &str| TYPO_PAIRS.iter().any(|(_, to)| cell.contains(*to));
+    let eligible = |cell: &str| has_typo(cell) || TYPO_PAIRS.iter().any(|(from, _)| cell.contains(*from));
+    let cells: Vec<&String> = rows.iter().flat_map(|row| row.iter().skip(1)).collect();
+    assert_eq!(summary.typos, cells.iter().filter(|cell| has_typo(cell)).count());
+    assert_rate(summary.typos, cells.iter().filter(|cell| eligible(cell)).count(), spec.typo_rate);
+  }
+
+  #[test]
+  fn sjis_rows_match_requested_rate() {
+    let spec = SampleDataSpec { sjis_rate: 0.2, ..clean_spec("sjis") };
+    let (summary, lines) = generate_bytes(&spec);
+
+    // 氏名列は常に漢字を含むため、Shift_JIS の行は UTF-8 として読めない
+    let sjis: Vec<&Vec<u8>> = lines.iter().filter(|line| std::str::from_utf8(line).is_err()).collect();
+    assert_eq!(lines.len(), spec.rows);
+    assert_eq!(summary.sjis_rows, sjis.len());
+    assert_rate(summary.sjis_rows, spec.rows, spec.sjis_rate);
+
+    let (decoded, _, had_errors) = encoding_rs::SHIFT_JIS.decode(sjis[0]);
+    assert!(!had_errors);
+    assert!(decoded.starts_with('C'));
+  }
+
+  #[test]
+  fn validate_rates_rejects_out_of_range_values() {
+    for rate in [f64::NAN, -0.1, 1.5, f64::INFINITY] {
+      let spec = SampleDataSpec {
+        missing_rate: rate,
+        ..SampleDataSpec::default()
+      };
+      assert!(validate_rates(&spec).is_err(), "{}", rate);
+    }
+    assert!(validate_rates(&SampleDataSpec::default()).is_ok());
+  }
+}
@@ -1,15 +1,47 @@
 //! ストア（設定ファイル）関連ロジックをまとめたモジュール
+//! - 現在のプロジェクト（`project_config`）
 //! - プロジェクト一覧（`projects`）
+//! - 最近開いたプロジェクト（`recent_projects`）
+//! - 設定スキーマのバージョン（`schema_version`）
+//! - 暗号化されたシークレット（`secrets`）
+//! - ワークスペース（`workspace`）
+//! - ファイルダイアログの既定フォルダ（`dialog_directories`）
 //! - ウィンドウ基本設定（`window_config`）
 //! - ウィンドウ状態（`window_state`）
+//! - パフォーマンスモード（`performance_mode`）
+//! - メインパネルのレイアウトプリセット（`layout_presets`）
+//! - データセットごとの列表示設定（`column_preferences`）
+//! - キーボードショートカット（`keybindings`）
+//! - ユーザー定義のカラーテーマ（`custom_themes`）
+//! - 使用中の設定プロファイル名（`active_profile`）
+//! - 設定の同期先フォルダ（`settings_sync`）
+//!
+//! あわせて、プロジェクトフォルダ内のプロジェクト別設定（`.d4cs/settings.json`）と、
+//! 設定ディレクトリ内の設定プロファイル（`profiles/<名前>.json`）も扱う
 
-use std::path::PathBuf;
+use std::{
+  collections::HashMap,
+  ffi::OsString,
+  fs,
+  io::Write,
+  path::{Path, PathBuf},
+  sync::{Arc, Mutex},
+};
 
-use log::{info};
+use aes_gcm::{
+  aead::{generic_array::GenericArray, Aead, AeadCore, KeyInit, OsRng},
+  Aes256Gcm,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Local, Utc};
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use tauri::{AppHandle};
-use tauri_plugin_store::StoreExt;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, WebviewWindow, Window, Wry};
+use tauri_plugin_store::{Store, StoreBuilder};
+
+use crate::{path_utils, safe_opener, system_monitor::PerformanceMode, write_access};
 
 /// プロジェクト情報（単一エントリ）
 /// フロントエンドから受け取ったり、一覧に追加したりするデータ構造
@@ -18,6 +50,141 @@ pub struct ProjectConfig {
   pub name: String,     // プロジェクト名
   pub filepath: String, // 保存パス
   pub remarks: String,  // 備考
+  #[serde(default)]
+  pub tags: Vec<String>, // タグ（検索・絞り込み用）
+  #[serde(default)]
+  pub description: String, // 説明（備考より長い概要）
+  #[serde(default)]
+  pub created_at: String, // 作成日時（RFC 3339、UTC）
+  #[serde(default)]
+  pub last_opened_at: Option<String>, // 最後に開いた日時（RFC 3339、UTC、未オープンなら None）
+  #[serde(default)]
+  pub pinned: bool, // お気に入り（一覧の先頭に表示）
+}
+
+/// ワークスペース
+/// 現在開いているプロジェクト（複数）とアクティブなプロジェクトを保持し、再起動後も復元する
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Workspace {
+  pub open_projects: Vec<String>, // 開いているプロジェクト名（開いた順）
+  pub active: Option<String>,     // アクティブなプロジェクト名
+}
+
+/// プロジェクト一覧の並び順
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectSort {
+  #[default]
+  Name, // プロジェクト名順
+  CreatedAt,    // 作成日時の新しい順
+  LastOpenedAt, // 最後に開いた日時の新しい順（未オープンは末尾）
+}
+
+/// プロジェクト別設定（`<プロジェクトフォルダ>/.d4cs/settings.json`）
+/// クレンジングルールやインポート設定をプロジェクトと一緒に持ち運べるようにする
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ProjectSettings {
+  pub cleaning_rules: Vec<Value>,                           // クレンジングルール（フロントエンドで定義した JSON）
+  pub import_presets: HashMap<String, Value>,               // プリセット名 → インポート設定
+  pub default_directories: HashMap<DialogCategory, String>, // 種別 → ダイアログの既定フォルダ（全体設定より優先）
+}
+
+/// ファイルダイアログの種別
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DialogCategory {
+  Import,  // データのインポート
+  Export,  // データのエクスポート
+  Project, // プロジェクトの作成・オープン
+}
+
+/// ファイルダイアログの既定フォルダ設定
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct DialogDirectories {
+  pub defaults: HashMap<DialogCategory, String>,  // 種別 → 既定フォルダ
+  pub remember_last_used: bool,                   // 最後に使ったフォルダを記憶するかどうか
+  pub last_used: HashMap<DialogCategory, String>, // 種別 → 最後に使ったフォルダ
+}
+
+impl Default for DialogDirectories {
+  fn default() -> Self {
+    DialogDirectories {
+      defaults: HashMap::new(),
+      remember_last_used: true,
+      last_used: HashMap::new(),
+    }
+  }
+}
+
+/// 暗号化されたシークレット（`secrets` の1エントリ）
+/// 値は AES-256-GCM で暗号化し、ノンスと暗号文を Base64 で保持する
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EncryptedSecret {
+  pub nonce: String,      // ノンス（Base64）
+  pub ciphertext: String, // 暗号文（Base64）
+}
+
+/// タグ一覧の1エントリ（タグ名と使用しているプロジェクト数）
+#[derive(Serialize, Clone, Debug)]
+pub struct TagSummary {
+  pub tag: String,  // タグ名
+  pub count: usize, // このタグが付いたプロジェクト数
+}
+
+/// ワークスペース検索の絞り込み条件
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct SearchFilters {
+  pub tags: Vec<String>, // すべて付いているプロジェクトのみ対象にするタグ
+}
+
+/// ワークスペース検索の結果（1件）
+#[derive(Serialize, Clone, Debug)]
+pub struct SearchResult {
+  pub kind: String,                // 種別（現在は "project" のみ）
+  pub name: String,                // 名前
+  pub filepath: String,            // 保存パス
+  pub matched_fields: Vec<String>, // 一致したフィールド（"name" / "filepath" / "remarks" / "tags"）
+}
+
+/// 設定ファイル名
+pub const CONFIG_FILE_NAME: &str = "D4CleaningStudio.config";
+
+/// 現在の設定スキーマバージョン
+/// 設定の構造に互換性のない変更（キー名の変更など）を加えた場合はインクリメントし、
+/// `MIGRATIONS` に移行処理を追加する
+pub const CURRENT_SCHEMA_VERSION: u64 = 1;
+
+/// スキーマ移行処理の一覧（移行先バージョン, 移行処理）
+/// 古いバージョンから順に適用される
+const MIGRATIONS: &[(u64, fn(&mut serde_json::Map<String, Value>))] = &[(1, migrate_to_v1)];
+
+// 最大化・最小化されていない時の最後のウィンドウサイズと位置
+// 最大化したまま終了した場合でも、元の大きさに戻せるように保持する
+static NORMAL_WINDOW_BOUNDS: once_cell::sync::Lazy<Mutex<Option<(PhysicalSize<u32>, PhysicalPosition<i32>)>>> = once_cell::sync::Lazy::new(|| Mutex::new(None));
+
+// アプリ自身が最後に書き込んだ設定ファイルの内容（JSON として解析したもの）
+// 設定ファイル監視で、自身の書き込みを外部変更と区別するために使う
+// 整形や改行コードの違いで誤検知しないよう、バイト列ではなく値で比較する
+static LAST_SAVED_CONTENT: once_cell::sync::Lazy<Mutex<Option<Value>>> = once_cell::sync::Lazy::new(|| Mutex::new(None));
+
+// ストアの変更・保存と、外部で変更された設定ファイルの再読み込みを直列化するロック
+// （`with_store` と `reload_external_change` で取得する）
+// 読み込み→変更→保存がこのロック内で完結するよう、変更前の値は `with_store` のクロージャ内で読み込む
+static STORE_LOCK: once_cell::sync::Lazy<Mutex<()>> = once_cell::sync::Lazy::new(|| Mutex::new(()));
+
+/// 最近開いたプロジェクト一覧に保持する最大件数
+pub const MAX_RECENT_PROJECTS: usize = 10;
+
+/// 最近開いたプロジェクト（MRU リストの1エントリ）
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecentProject {
+  pub name: String,      // プロジェクト名
+  pub filepath: String,  // 保存パス（重複判定のキー）
+  pub opened_at: String, // 最後に開いた日時（RFC 3339、UTC）
 }
 
 /// ウィンドウ基本設定
@@ -50,22 +217,149 @@ pub struct MainPanelLayout {
   pub vertical: [u32; 2],   // 垂直レイアウトの比率
 }
 
+/// 名前付きレイアウトプリセット
+/// メインパネルの分割比率に名前を付けて保存し、メニューから切り替える
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LayoutPreset {
+  pub name: String,            // プリセット名（例: "Profiling"）
+  pub layout: MainPanelLayout, // パネルレイアウト
+}
+
+/// キーボードショートカットが有効な範囲
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeybindingContext {
+  Global,      // アプリ全体
+  FlowEditor,  // フローエディター
+  ProjectTree, // プロジェクトツリー
+}
+
+impl KeybindingContext {
+  /// 同じキーを割り当てると衝突する範囲かどうか（アプリ全体のショートカットはすべてと衝突する）
+  fn overlaps(self, other: KeybindingContext) -> bool {
+    self == other || self == KeybindingContext::Global || other == KeybindingContext::Global
+  }
+}
+
+/// キーボードショートカットの割り当て
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Keybinding {
+  pub action: String,             // 操作 ID（例: "flow_editor.undo"）
+  pub context: KeybindingContext, // 有効な範囲
+  pub shortcut: String,           // キーの組み合わせ（例: "Ctrl+Shift+S"、空文字は割り当てなし）
+}
+
+/// 組み込みテーマ名（`WindowState.theme` に保存される値）
+pub const BUILTIN_THEMES: &[&str] = &["Light", "Dark", "auto"];
+
+/// テーマの色定義（DaisyUI のカラー名に対応）
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ThemeColors {
+  pub primary: String,
+  pub secondary: String,
+  pub accent: String,
+  pub neutral: String,
+  pub base_100: String,
+  pub base_200: String,
+  pub base_300: String,
+  pub base_content: String,
+  pub info: String,
+  pub success: String,
+  pub warning: String,
+  pub error: String,
+}
+
+impl ThemeColors {
+  /// 色名と値の組の一覧（検証・エラー表示用）
+  fn entries(&self) -> [(&'static str, &str); 12] {
+    [
+      ("primary", self.primary.as_str()),
+      ("secondary", self.secondary.as_str()),
+      ("accent", self.accent.as_str()),
+      ("neutral", self.neutral.as_str()),
+      ("base_100", self.base_100.as_str()),
+      ("base_200", self.base_200.as_str()),
+      ("base_300", self.base_300.as_str()),
+      ("base_content", self.base_content.as_str()),
+      ("info", self.info.as_str()),
+      ("success", self.success.as_str()),
+      ("warning", self.warning.as_str()),
+      ("error", self.error.as_str()),
+    ]
+  }
+}
+
+/// ユーザー定義のカラーテーマ
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CustomTheme {
+  pub name: String,        // テーマ名（`WindowState.theme` に保存される）
+  pub dark: bool,          // 暗い配色かどうか（スクロールバーなど OS 側の表示に使う）
+  pub colors: ThemeColors, // 色定義
+}
+
+/// 設定の同期（利用者が選んだフォルダへのミラー）
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SettingsSync {
+  pub enabled: bool,          // 同期を有効にするかどうか（既定は無効）
+  pub folder: Option<String>, // 同期先フォルダ（Dropbox / OneDrive のフォルダなど）
+}
+
+/// 同期の競合時にどちらを優先するか
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncPreference {
+  Local,  // このマシンの設定で同期先を上書き
+  Remote, // 同期先の設定でこのマシンの設定を上書き
+}
+
+/// 同期の結果
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SyncOutcome {
+  UpToDate, // 変更なし
+  Pushed,   // このマシンの設定を同期先へ書き出した
+  Pulled,   // 同期先の設定を読み込んだ
+  // 前回の同期以降に両方が変更された
+  Conflict {
+    local_modified: String,  // このマシンの設定ファイルの更新日時（RFC 3339）
+    remote_modified: String, // 同期先の設定ファイルの更新日時（RFC 3339）
+  },
+}
+
+/// 列表示設定（グリッド表示の好み）
+/// 同じ列構成のファイルを再度開いた時に自動で復元する
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ColumnDisplayPreferences {
+  pub widths: HashMap<String, u32>,     // 列名 → 列幅（px）
+  pub hidden: Vec<String>,              // 非表示の列名
+  pub frozen: Vec<String>,              // 固定表示の列名
+  pub formats: HashMap<String, String>, // 列名 → 数値・日付の表示フォーマット
+}
+
 /// 全体設定構造体
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Config {
-  pub projects: ProjectConfig,
+  pub projects: Vec<ProjectConfig>,
+  pub recent_projects: Vec<RecentProject>,
+  pub workspace: Workspace,
+  pub dialog_directories: DialogDirectories,
   pub window_state: WindowState,
   pub window_config: WindowConfig,
+  pub performance_mode: PerformanceMode,
+  pub layout_presets: Vec<LayoutPreset>,
+  pub keybindings: Vec<Keybinding>,
+  pub custom_themes: Vec<CustomTheme>,
+  pub settings_sync: SettingsSync,
 }
 
 impl Default for Config {
   fn default() -> Self {
     Config {
-      projects: ProjectConfig {
-        name: String::from(""),
-        filepath: String::from(""),
-        remarks: String::from(""),
-      },
+      projects: Vec::new(),
+      recent_projects: Vec::new(),
+      workspace: Workspace::default(),
+      dialog_directories: DialogDirectories::default(),
       window_state: WindowState {
         width: 1200,
         height: 800,
@@ -85,22 +379,198 @@ impl Default for Config {
         max_width: 7680,
         max_height: 4320,
       },
+      performance_mode: PerformanceMode::default(),
+      layout_presets: vec![
+        LayoutPreset {
+          name: String::from("Profiling"),
+          layout: MainPanelLayout {
+            horizontal: [20, 60, 20],
+            vertical: [70, 30],
+          },
+        },
+        LayoutPreset {
+          name: String::from("Review"),
+          layout: MainPanelLayout {
+            horizontal: [15, 70, 15],
+            vertical: [85, 15],
+          },
+        },
+        LayoutPreset {
+          name: String::from("Compare"),
+          layout: MainPanelLayout {
+            horizontal: [10, 80, 10],
+            vertical: [60, 40],
+          },
+        },
+      ],
+      keybindings: default_keybindings(),
+      custom_themes: Vec::new(),
+      settings_sync: SettingsSync::default(),
+    }
+  }
+}
+
+/// キーボードショートカットの初期値（フロントエンドの既存ショートカットに合わせる）
+fn default_keybindings() -> Vec<Keybinding> {
+  use KeybindingContext::{FlowEditor, ProjectTree};
+  [
+    ("flow_editor.undo", FlowEditor, "Ctrl+Z"),
+    ("flow_editor.redo", FlowEditor, "Ctrl+Y"),
+    ("flow_editor.save", FlowEditor, "Ctrl+S"),
+    ("flow_editor.save_as", FlowEditor, "Ctrl+Shift+S"),
+    ("flow_editor.open", FlowEditor, "Ctrl+O"),
+    ("flow_editor.new", FlowEditor, "Ctrl+N"),
+    ("flow_editor.reset", FlowEditor, "Ctrl+R"),
+    ("flow_editor.copy", FlowEditor, "Ctrl+C"),
+    ("flow_editor.cut", FlowEditor, "Ctrl+X"),
+    ("flow_editor.paste", FlowEditor, "Ctrl+V"),
+    ("flow_editor.delete", FlowEditor, "Delete"),
+    ("project_tree.new_file", ProjectTree, "Ctrl+N"),
+    ("project_tree.new_folder", ProjectTree, "Ctrl+Shift+N"),
+    ("project_tree.refresh", ProjectTree, "F5"),
+    ("project_tree.collapse", ProjectTree, "Alt+-"),
+    ("project_tree.copy", ProjectTree, "Ctrl+C"),
+    ("project_tree.cut", ProjectTree, "Ctrl+X"),
+    ("project_tree.paste", ProjectTree, "Ctrl+V"),
+    ("project_tree.rename", ProjectTree, "F2"),
+    ("project_tree.delete", ProjectTree, "Delete"),
+  ]
+  .into_iter()
+  .map(|(action, context, shortcut)| Keybinding {
+    action: action.to_string(),
+    context,
+    shortcut: shortcut.to_string(),
+  })
+  .collect()
+}
+
+/// 設定のリセット範囲
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetScope {
+  WindowState,   // ウィンドウ状態のみ
+  WindowConfig,  // ウィンドウ設定のみ
+  ProjectConfig, // 現在のプロジェクト設定のみ
+  All,           // 全体設定のすべての設定キー（プロジェクト一覧などの利用者のデータは残す）
+}
+
+/// `settings-reset` イベントのペイロード
+#[derive(Serialize, Clone, Debug)]
+pub struct SettingsResetPayload {
+  pub scope: ResetScope, // リセットした範囲
+  pub keys: Vec<String>, // デフォルト値に戻したキー
+}
+
+/// ストア変更通知のイベント名
+pub const STORE_CHANGED_EVENT: &str = "store://changed";
+
+/// `store://changed` イベントのペイロード
+/// 暗号化されたシークレット（`secrets`）は値を送らず null にする
+#[derive(Serialize, Clone, Debug)]
+pub struct StoreChangedPayload {
+  pub key: String,  // 変更されたキー
+  pub value: Value, // 新しい値（削除された場合は null）
+}
+
+/// 既定の設定プロファイル名
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// 設定プロファイルの一覧
+#[derive(Serialize, Clone, Debug)]
+pub struct ProfileList {
+  pub active: String,        // 使用中のプロファイル名
+  pub profiles: Vec<String>, // すべてのプロファイル名（名前順）
+}
+
+/// 設定ディレクトリを上書きする環境変数名
+pub const CONFIG_DIR_ENV: &str = "D4CS_CONFIG_DIR";
+
+/// 設定ディレクトリを上書きするコマンドライン引数名
+pub const CONFIG_DIR_ARG: &str = "--config-dir";
+
+/// ポータブルモードを有効にするフラグファイル名（実行ファイルと同じフォルダに置く）
+pub const PORTABLE_FLAG_FILE: &str = "portable.flag";
+
+/// ポータブルモードで設定・ログを保存するフォルダ名（実行ファイルと同じフォルダに作る）
+pub const PORTABLE_DATA_DIR: &str = "data";
+
+// ポータブルモードの設定ディレクトリ（実行ファイルの隣に `portable.flag` がある場合のみ、起動時に一度だけ解決）
+static PORTABLE_CONFIG_DIR: once_cell::sync::Lazy<Option<PathBuf>> = once_cell::sync::Lazy::new(|| {
+  let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+  exe_dir.join(PORTABLE_FLAG_FILE).is_file().then(|| exe_dir.join(PORTABLE_DATA_DIR))
+});
+
+// コマンドライン引数・環境変数で指定された設定ディレクトリ（起動時に一度だけ解決）
+static CONFIG_DIR_OVERRIDE: once_cell::sync::Lazy<Option<PathBuf>> = once_cell::sync::Lazy::new(|| {
+  config_dir_from(std::env::args_os().skip(1), std::env::var_os(CONFIG_DIR_ENV)).map(|dir| {
+    if dir.is_relative() {
+      std::env::current_dir().map(|cwd| cwd.join(&dir)).unwrap_or(dir)
+    } else {
+      dir
+    }
+  })
+});
+
+/// コマンドライン引数から `--config-dir <path>` / `--config-dir=<path>` を取り出す
+/// UTF-8 でない引数（ファイルの関連付けで渡されたパスなど）があっても失敗しない
+/// （`--config-dir=<path>` 形式は UTF-8 のパスのみ。それ以外は `--config-dir <path>` で指定する）
+fn config_dir_from_args(mut args: impl Iterator<Item = OsString>) -> Option<PathBuf> {
+  while let Some(arg) = args.next() {
+    if arg == CONFIG_DIR_ARG {
+      return args.next().filter(|v| !v.is_empty()).map(PathBuf::from);
+    }
+    if let Some(value) = arg.to_str().and_then(|arg| arg.strip_prefix(CONFIG_DIR_ARG)).and_then(|rest| rest.strip_prefix('=')) {
+      return Some(PathBuf::from(value)).filter(|v| !v.as_os_str().is_empty());
     }
   }
+  None
+}
+
+/// コマンドライン引数、なければ環境変数 `D4CS_CONFIG_DIR` の値から設定ディレクトリを決める
+fn config_dir_from(args: impl Iterator<Item = OsString>, env: Option<OsString>) -> Option<PathBuf> {
+  config_dir_from_args(args).or_else(|| env.filter(|v| !v.is_empty()).map(PathBuf::from))
+}
+
+/// 設定ディレクトリを取得
+/// 優先順位: `--config-dir` 引数 > 環境変数 `D4CS_CONFIG_DIR` > ポータブルモードの `data/` > ユーザー設定ディレクトリ配下のアプリ専用ディレクトリ
+/// ログの出力先もこのディレクトリを使う
+pub fn resolve_config_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+  if let Some(dir) = CONFIG_DIR_OVERRIDE.as_ref() {
+    return Ok(dir.clone());
+  }
+  if let Some(dir) = PORTABLE_CONFIG_DIR.as_ref() {
+    return Ok(dir.clone()); // <実行ファイルのフォルダ>/data
+  }
+  match dirs_2::config_dir() {
+    Some(dir) => Ok(dir.join("D4CleaningStudio")), // %APPDATA%/D4CleaningStudio (Windows)
+    None => Err("設定ディレクトリの取得に失敗しました".into()),
+  }
+}
+
+/// ポータブルモード（実行ファイルの隣に `portable.flag` がある）で起動しているかどうか
+pub fn is_portable() -> bool {
+  PORTABLE_CONFIG_DIR.is_some()
 }
+
 /// ストア管理ユーティリティ
 /// 設定ディレクトリの作成、キーのデフォルト初期化、
 /// 読み込み・書き込み操作をまとめて提供する
 pub fn initialize_store(app: &AppHandle, config_dir: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
   // 設定ディレクトリを生成（既に存在していても OK）
-  std::fs::create_dir_all(config_dir)?;
-  
+  std::fs::create_dir_all(path_utils::for_io(config_dir))?;
+
   // 設定ファイルのフルパスを構築
-  let path = config_dir.join("D4CleaningStudio.config");
+  let path = config_dir.join(CONFIG_FILE_NAME);
   info!("設定ファイルのパス: {}", path.display());
-  
+
+  // 設定ファイルが壊れている場合はバックアップから復元
+  recover_config_file(&path)?;
+
   // Tauri のストアハンドルを取得
-  let store = app.store(path.to_string_lossy().as_ref())?;
+  let store = open_store(app, &path)?;
+
+  // 古い設定ファイルを現在のスキーマへ移行
+  migrate_store(&store)?;
 
   // デフォルト設定を取得
   let default_config = Config::default();
@@ -108,71 +578,2589 @@ pub fn initialize_store(app: &AppHandle, config_dir: &PathBuf) -> Result<(), Box
   // ── project_config の初期化 ─────────────────────────
   // キー "project_config" が存在しない場合、デフォルト値を設定
   if !store.has("project_config") {
-    store.set(
-      "project_config",
-      json!(default_config.projects),
-    );
+    store.set("project_config", json!(ProjectConfig::default()));
     info!("project_config をデフォルト初期化");
   }
 
+  // ── projects の初期化 ──────────────────────────────
+  // キー "projects" が存在しない場合、デフォルト値を設定
+  // （従来の単一プロジェクト設定からの引き継ぎはスキーマ移行で行う）
+  if !store.has("projects") {
+    store.set("projects", json!(default_config.projects));
+    info!("projects をデフォルト初期化");
+  }
+
+  // ── recent_projects の初期化 ───────────────────────
+  // キー "recent_projects" が存在しない場合、デフォルト値を設定
+  if !store.has("recent_projects") {
+    store.set("recent_projects", json!(default_config.recent_projects));
+    info!("recent_projects をデフォルト初期化");
+  }
+
+  // ── workspace の初期化 ─────────────────────────────
+  // キー "workspace" が存在しない場合、デフォルト値を設定
+  if !store.has("workspace") {
+    store.set("workspace", json!(default_config.workspace));
+    info!("workspace をデフォルト初期化");
+  }
+
+  // ── dialog_directories の初期化 ────────────────────
+  // キー "dialog_directories" が存在しない場合、デフォルト値を設定
+  if !store.has("dialog_directories") {
+    store.set("dialog_directories", json!(default_config.dialog_directories));
+    info!("dialog_directories をデフォルト初期化");
+  }
+
   // ── window_config の初期化 ──────────────────────────
   // キー "window_config" が存在しない場合、デフォルト値を設定
   if !store.has("window_config") {
-    store.set(
-      "window_config",
-      json!(default_config.window_config),
-    );
+    store.set("window_config", json!(default_config.window_config));
     info!("window_config をデフォルト初期化");
   }
 
   // ── window_state の初期化 ──────────────────────────
   // キー "window_state" が存在しない場合、デフォルト値を設定
   if !store.has("window_state") {
-    store.set(
-      "window_state",
-      json!(default_config.window_state),
-    );
+    store.set("window_state", json!(default_config.window_state));
     info!("window_state をデフォルト初期化");
   }
 
-  // 設定をディスクに書き込み、リソースを解放
-  store.save()?;
-  store.close_resource();
+  // ── performance_mode の初期化 ──────────────────────
+  // キー "performance_mode" が存在しない場合、デフォルト値を設定
+  if !store.has("performance_mode") {
+    store.set("performance_mode", json!(default_config.performance_mode));
+    info!("performance_mode をデフォルト初期化");
+  }
+
+  // ── layout_presets の初期化 ────────────────────────
+  // キー "layout_presets" が存在しない場合、デフォルト値を設定
+  if !store.has("layout_presets") {
+    store.set("layout_presets", json!(default_config.layout_presets));
+    info!("layout_presets をデフォルト初期化");
+  }
+
+  // ── keybindings の初期化 ───────────────────────────
+  // キー "keybindings" が存在しない場合、デフォルト値を設定
+  if !store.has("keybindings") {
+    store.set("keybindings", json!(default_config.keybindings));
+    info!("keybindings をデフォルト初期化");
+  }
+
+  // ── custom_themes の初期化 ─────────────────────────
+  // キー "custom_themes" が存在しない場合、デフォルト値を設定
+  if !store.has("custom_themes") {
+    store.set("custom_themes", json!(default_config.custom_themes));
+    info!("custom_themes をデフォルト初期化");
+  }
+
+  // ── settings_sync の初期化 ─────────────────────────
+  // キー "settings_sync" が存在しない場合、デフォルト値を設定
+  if !store.has("settings_sync") {
+    store.set("settings_sync", json!(default_config.settings_sync));
+    info!("settings_sync をデフォルト初期化");
+  }
+
+  // 範囲外の値をデフォルト値へ修復
+  repair_store(app, &store)?;
+
+  // 設定をディスクに書き込み、以降の読み書きで使い回せるよう共有ハンドルとして保持
+  save_store(&store, &path)?;
+  app.manage(SharedStore { path, store });
   Ok(())
 }
 
-/// プロジェクト設定を読み込み
-pub fn load_project_config(app: &AppHandle, config_dir: &PathBuf) -> Result<ProjectConfig, Box<dyn std::error::Error>> {
-  let path = config_dir.join("D4CleaningStudio.config");
-  let store = app.store(path.to_string_lossy().as_ref())?;
-  let cfg: ProjectConfig = match store.get("project_config") {
-    Some(v) => serde_json::from_value(v.clone())?,
-    None => return Err("project_config が存在しません".into()),
+/// 設定ファイルのバックアップのパス（`D4CleaningStudio.config.bak`）
+fn backup_path(path: &Path) -> PathBuf {
+  let mut name = path.as_os_str().to_owned();
+  name.push(".bak");
+  PathBuf::from(name)
+}
+
+/// ファイルが JSON として読み込めるかどうか
+fn is_valid_json(path: &Path) -> bool {
+  match fs::read(path_utils::for_io(path)) {
+    Ok(bytes) => serde_json::from_slice::<Value>(&bytes).is_ok(),
+    Err(_) => false,
+  }
+}
+
+/// ストアの内容を設定ファイルへアトミックに書き込む
+/// 1. 現在の設定ファイルが正常なら `.bak` として退避
+/// 2. 一時ファイルへ書き込み、ディスクへ同期
+/// 3. 一時ファイルを設定ファイルへリネーム（書き込み途中で落ちても壊れたファイルが残らない）
+pub fn save_store(store: &Store<Wry>, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+  let entries: serde_json::Map<String, Value> = store.entries().into_iter().collect();
+  let bytes = serde_json::to_vec_pretty(&entries)?;
+
+  if is_valid_json(path) {
+    fs::copy(path_utils::for_io(path), path_utils::for_io(&backup_path(path)))?;
+  }
+  if let Ok(mut last) = LAST_SAVED_CONTENT.lock() {
+    *last = Some(Value::Object(entries));
+  }
+  write_file_atomic(path, &bytes)
+}
+
+/// 共有ストアハンドル（アプリの管理状態として保持）
+/// 呼び出しのたびにストアを開いて設定ファイルを読み直さず、起動時に読み込んだハンドルを使い回す
+/// `Store` への参照として扱えるほか、よく使うキーの型付きゲッターを持つ
+#[derive(Clone)]
+pub struct SharedStore {
+  path: PathBuf,          // 設定ファイルのパス
+  store: Arc<Store<Wry>>, // ストア本体
+}
+
+impl SharedStore {
+  /// キーの値を型を指定して取得
+  pub fn get_as<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<T, Box<dyn std::error::Error>> {
+    match self.store.get(key) {
+      Some(value) => Ok(serde_json::from_value(value)?),
+      None => Err(format!("{} が存在しません", key).into()),
+    }
+  }
+
+  /// 現在のプロジェクト設定
+  pub fn project_config(&self) -> Result<ProjectConfig, Box<dyn std::error::Error>> {
+    self.get_as("project_config")
+  }
+
+  /// ウィンドウ基本設定
+  pub fn window_config(&self) -> Result<WindowConfig, Box<dyn std::error::Error>> {
+    self.get_as("window_config")
+  }
+
+  /// ウィンドウ状態
+  pub fn window_state(&self) -> Result<WindowState, Box<dyn std::error::Error>> {
+    self.get_as("window_state")
+  }
+}
+
+impl std::ops::Deref for SharedStore {
+  type Target = Store<Wry>;
+
+  fn deref(&self) -> &Store<Wry> {
+    &self.store
+  }
+}
+
+/// 共有ストアハンドルを取得
+/// 初期化済みの管理状態があればそれを返し、初期化前や別の設定ディレクトリの場合はストアを開く
+pub fn shared_store(app: &AppHandle, config_dir: &Path) -> Result<SharedStore, Box<dyn std::error::Error>> {
+  let path = config_dir.join(CONFIG_FILE_NAME);
+  if let Some(shared) = app.try_state::<SharedStore>() {
+    if shared.path == path {
+      return Ok(shared.inner().clone());
+    }
+  }
+  let store = open_store(app, &path)?;
+  Ok(SharedStore { path, store })
+}
+
+/// 自動保存を無効にしてストアを開く（既に開いている場合はそのハンドルを返す）
+/// プラグインの自動保存はアトミックでない書き込みを行うため、保存は `save_store` だけで行う
+fn open_store(app: &AppHandle, path: &Path) -> Result<Arc<Store<Wry>>, Box<dyn std::error::Error>> {
+  Ok(StoreBuilder::new(app, path).disable_auto_save().build()?)
+}
+
+/// キーの値を型を指定して読み込む（キーが無い場合は None）
+/// 読み込み→変更→保存を行う関数は、`with_store` のクロージャ内でこの関数を使って変更前の値を読み込む
+/// （ロックの外で読み込むと、その間に保存された他の変更を上書きしてしまう）
+fn read_key<T: serde::de::DeserializeOwned>(store: &Store<Wry>, key: &str) -> Result<Option<T>, Box<dyn std::error::Error>> {
+  match store.get(key) {
+    Some(value) => Ok(Some(serde_json::from_value(value)?)),
+    None => Ok(None),
+  }
+}
+
+/// ストアへの一連の変更をまとめて適用し、最後に一度だけ保存する
+/// 実行中は書き込みロックを保持するため、他の `with_store` 呼び出しとは同時に実行されない
+/// クロージャまたは保存がエラーを返した場合はストアを実行前の内容に戻す
+/// 保存後、値が変わったキーごとに `store://changed` イベントを送信する
+/// （ロックは再入できないため、クロージャ内から `with_store` を使う関数を呼ばないこと）
+pub fn with_store<T>(app: &AppHandle, config_dir: &PathBuf, f: impl FnOnce(&Store<Wry>) -> Result<T, Box<dyn std::error::Error>>) -> Result<T, Box<dyn std::error::Error>> {
+  let _guard = STORE_LOCK.lock().map_err(|_| "ストアのロック取得に失敗しました")?;
+  let path = config_dir.join(CONFIG_FILE_NAME);
+  let store = shared_store(app, config_dir)?;
+  let snapshot = store.entries();
+
+  let result = f(&*store).and_then(|result| {
+    save_store(&store, &path)?;
+    Ok(result)
+  });
+  match result {
+    Ok(result) => {
+      emit_store_changes(app, snapshot, store.entries());
+      Ok(result)
+    },
+    Err(e) => {
+      restore_entries(&store, snapshot);
+      warn!("ストアの変更を取り消しました: {}", e);
+      Err(e)
+    },
+  }
+}
+
+/// ストアの内容を `snapshot` の時点に戻す
+fn restore_entries(store: &Store<Wry>, snapshot: Vec<(String, Value)>) {
+  store.clear();
+  for (key, value) in snapshot {
+    store.set(key, value);
+  }
+}
+
+/// 変更前後のストアを比較し、値が変わったキーを `store://changed` イベントで通知
+fn emit_store_changes(app: &AppHandle, before: Vec<(String, Value)>, after: Vec<(String, Value)>) {
+  let mut before: HashMap<String, Value> = before.into_iter().collect();
+  let mut changes: Vec<(String, Value)> = Vec::new();
+  for (key, value) in after {
+    if before.remove(&key).as_ref() != Some(&value) {
+      changes.push((key, value));
+    }
+  }
+  // 変更後に存在しないキーは削除されたもの
+  changes.extend(before.into_keys().map(|key| (key, Value::Null)));
+
+  for (key, value) in changes {
+    let value = if key == "secrets" { Value::Null } else { value };
+    if let Err(e) = app.emit(STORE_CHANGED_EVENT, StoreChangedPayload { key: key.clone(), value }) {
+      error!("{} イベントの送信に失敗しました ({}): {}", STORE_CHANGED_EVENT, key, e);
+    }
+  }
+}
+
+/// 複数のキーをまとめて更新し、一度だけ保存する
+/// 既知のキーは型を検証し、一つでも不正な値があれば何も変更しない
+///
+/// # 戻り値
+/// * 更新したキーの一覧
+pub fn set_many(app: &AppHandle, config_dir: &PathBuf, updates: serde_json::Map<String, Value>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+  validate_settings(&updates)?;
+  let keys: Vec<String> = updates.keys().cloned().collect();
+  with_store(app, config_dir, |tx| {
+    for (key, value) in updates {
+      tx.set(key, value);
+    }
+    Ok(())
+  })?;
+  info!("設定をまとめて更新しました: {:?}", keys);
+  Ok(keys)
+}
+
+/// 設定ファイルの内容がアプリ自身の最後の書き込みと一致するかどうか
+fn is_own_write(content: &Value) -> bool {
+  LAST_SAVED_CONTENT.lock().map(|last| last.as_ref() == Some(content)).unwrap_or(false)
+}
+
+/// 外部で変更された設定ファイルをストアへ再読み込みする
+/// 書き込みロックを保持したまま読み込み・比較・再読み込みを行い、`with_store` による保存と競合しないようにする
+/// アプリ自身の書き込みや、書き込み途中の不完全な JSON は無視する
+/// 読み込む前にスキーマ移行と型の検証を行い、不正な内容は読み込まずに現在の設定を維持する
+/// 範囲外の値は `repair_store` で修復し、修復した場合は設定ファイルにも書き戻す
+/// 再読み込み後、`with_store` と同様に値が変わったキーごとに `store://changed` イベントを送信する
+///
+/// # 戻り値
+/// * 再読み込みした場合は true
+pub fn reload_external_change(app: &AppHandle, config_dir: &Path) -> Result<bool, Box<dyn std::error::Error>> {
+  let _guard = STORE_LOCK.lock().map_err(|_| "ストアのロック取得に失敗しました")?;
+  let path = config_dir.join(CONFIG_FILE_NAME);
+  let content = match fs::read(path_utils::for_io(&path)).ok().and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok()) {
+    Some(content) => content,
+    None => return Ok(false),
   };
-  info!("プロジェクト設定を読み込みました: {:?}", cfg);
-  Ok(cfg)
+  if is_own_write(&content) {
+    return Ok(false);
+  }
+
+  let mut entries = match &content {
+    Value::Object(map) => map.clone(),
+    _ => {
+      warn!("外部で変更された設定ファイルが JSON オブジェクトではないため読み込みませんでした");
+      return Ok(false);
+    },
+  };
+  let checked = migrate_entries(&mut entries).and_then(|migrated| {
+    if !migrated {
+      return Err("スキーマバージョンがこのバージョンより新しい設定ファイルです".into());
+    }
+    validate_settings(&entries)
+  });
+  if let Err(e) = checked {
+    warn!("外部で変更された設定ファイルを読み込まず、現在の設定を維持しました: {}", e);
+    return Ok(false);
+  }
+
+  // 検証済みの内容をストアへ反映し、範囲外の値を修復する
+  let store = shared_store(app, config_dir)?;
+  let snapshot = store.entries();
+  store.clear();
+  for (key, value) in entries {
+    store.set(key, value);
+  }
+  let committed = repair_store(app, &store).and_then(|_| {
+    let loaded = Value::Object(store.entries().into_iter().collect());
+    if loaded == content {
+      if let Ok(mut last) = LAST_SAVED_CONTENT.lock() {
+        *last = Some(content);
+      }
+      Ok(())
+    } else {
+      save_store(&store, &path)
+    }
+  });
+  if let Err(e) = committed {
+    restore_entries(&store, snapshot);
+    warn!("外部で変更された設定ファイルの読み込みを取り消しました: {}", e);
+    return Err(e);
+  }
+  emit_store_changes(app, snapshot, store.entries());
+  Ok(true)
 }
 
-/// ウィンドウ基本設定を読み込み
-pub fn load_window_config(app: &AppHandle, config_dir: &PathBuf) -> Result<WindowConfig, Box<dyn std::error::Error>> {
-  let path = config_dir.join("D4CleaningStudio.config");
-  let store = app.store(path.to_string_lossy().as_ref())?;
-  let cfg: WindowConfig = match store.get("window_config") {
-    Some(v) => serde_json::from_value(v.clone())?,
-    None => return Err("window_config が存在しません".into()),
+/// 一時ファイルへ書き込んでからリネームすることで、ファイルをアトミックに置き換える
+fn write_file_atomic(path: &Path, bytes: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+  path_utils::validate(path)?;
+  let path = path_utils::for_io(path);
+  let mut tmp_name = path.as_os_str().to_owned();
+  tmp_name.push(".tmp");
+  let tmp_path = PathBuf::from(tmp_name);
+  {
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+  }
+  fs::rename(&tmp_path, &path)?;
+  Ok(())
+}
+
+/// 壊れた設定ファイルをバックアップから復元
+/// バックアップも使えない場合は壊れたファイルを `.corrupt` として退避し、デフォルト値で作り直させる
+fn recover_config_file(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+  if !path_utils::for_io(path).exists() || is_valid_json(path) {
+    return Ok(());
+  }
+
+  let backup = backup_path(path);
+  if is_valid_json(&backup) {
+    fs::copy(path_utils::for_io(&backup), path_utils::for_io(path))?;
+    warn!("設定ファイルが破損していたため、バックアップから復元しました: {}", backup.display());
+  } else {
+    let mut corrupt_name = path.as_os_str().to_owned();
+    corrupt_name.push(".corrupt");
+    fs::rename(path_utils::for_io(path), path_utils::for_io(Path::new(&corrupt_name)))?;
+    error!("設定ファイルが破損しており、有効なバックアップもないためデフォルト設定で作り直します");
+  }
+  Ok(())
+}
+
+/// 画面内に見えている必要があるウィンドウの最小幅・高さ（px）
+/// これより小さい範囲しかモニターと重ならない場合は画面外とみなす
+const MIN_VISIBLE_WINDOW_AREA: i64 = 100;
+
+/// 2つの区間（開始位置と長さ）が重なる長さ（重ならなければ 0 以下）
+/// 設定ファイルの値が極端でもオーバーフローしないよう i64 で計算する
+fn overlap_length(start: i32, length: u32, other_start: i32, other_length: u32) -> i64 {
+  let (start, other_start) = (i64::from(start), i64::from(other_start));
+  (start + i64::from(length)).min(other_start + i64::from(other_length)) - start.max(other_start)
+}
+
+/// パネル比率の合計を 100 に揃える
+/// フロントエンドは比率を整数に丸めて保存するため、パネル数 - 1 までの誤差は最も大きいパネルで吸収する
+/// 誤差がそれより大きい場合は false を返す
+fn normalize_ratios(ratios: &mut [u32]) -> bool {
+  let sum: i64 = ratios.iter().map(|&r| i64::from(r)).sum();
+  let diff = 100 - sum;
+  if diff == 0 {
+    return true;
+  }
+  if diff.unsigned_abs() >= ratios.len() as u64 {
+    return false;
+  }
+  match ratios.iter_mut().max() {
+    Some(largest) => {
+      *largest = (i64::from(*largest) + diff) as u32;
+      true
+    },
+    None => false,
+  }
+}
+
+/// 設定値を検証し、不正な値をデフォルト値へ修復
+/// - ウィンドウの最小サイズ ≦ 最大サイズ
+/// - ウィンドウサイズが最小・最大サイズの範囲内
+/// - パネル比率の合計が 100
+/// - テーマが組み込みテーマまたは定義済みのカスタムテーマ
+/// - ウィンドウがいずれかのモニター上に見えている
+fn repair_store(app: &AppHandle, store: &Store<Wry>) -> Result<(), Box<dyn std::error::Error>> {
+  let defaults = Config::default();
+
+  // ── window_config ─────────────────────────────────
+  let mut window_config: WindowConfig = match store.get("window_config").map(serde_json::from_value) {
+    Some(Ok(cfg)) => cfg,
+    _ => {
+      warn!("window_config が読み込めないためデフォルト値に戻しました");
+      defaults.window_config.clone()
+    },
   };
-  info!("ウィンドウ設定を読み込みました: {:?}", cfg);
-  Ok(cfg)
+  if window_config.min_width == 0 || window_config.min_height == 0 || window_config.min_width > window_config.max_width || window_config.min_height > window_config.max_height {
+    warn!("window_config の最小・最大サイズが不正なためデフォルト値に戻しました: {:?}", window_config);
+    window_config = defaults.window_config.clone();
+  }
+  store.set("window_config", json!(window_config));
+
+  // ── window_state ──────────────────────────────────
+  let mut state: WindowState = match store.get("window_state").map(serde_json::from_value) {
+    Some(Ok(st)) => st,
+    _ => {
+      warn!("window_state が読み込めないためデフォルト値に戻しました");
+      defaults.window_state.clone()
+    },
+  };
+
+  let width = state.width.clamp(window_config.min_width, window_config.max_width);
+  let height = state.height.clamp(window_config.min_height, window_config.max_height);
+  if (width, height) != (state.width, state.height) {
+    warn!("ウィンドウサイズ {}x{} を {}x{} に修正しました", state.width, state.height, width, height);
+    state.width = width;
+    state.height = height;
+  }
+
+  let horizontal_ok = normalize_ratios(&mut state.main_panel_layout.horizontal);
+  let vertical_ok = normalize_ratios(&mut state.main_panel_layout.vertical);
+  if !(horizontal_ok && vertical_ok) {
+    warn!("パネル比率の合計が 100 ではないためデフォルト値に戻しました: {:?}", state.main_panel_layout);
+    state.main_panel_layout = defaults.window_state.main_panel_layout.clone();
+  }
+
+  let custom_themes: Vec<CustomTheme> = store.get("custom_themes").and_then(|v| serde_json::from_value(v).ok()).unwrap_or_default();
+  if !BUILTIN_THEMES.contains(&state.theme.as_str()) && !custom_themes.iter().any(|t| t.name == state.theme) {
+    warn!("不明なテーマ '{}' を auto に戻しました", state.theme);
+    state.theme = defaults.window_state.theme.clone();
+  }
+
+  // モニター情報が取得できない場合（ヘッドレス環境など）は位置の検証を行わない
+  if let Ok(monitors) = app.available_monitors() {
+    let visible = monitors.iter().any(|m| {
+      let (pos, size) = (m.position(), m.size());
+      let overlap_x = overlap_length(state.x, state.width, pos.x, size.width);
+      let overlap_y = overlap_length(state.y, state.height, pos.y, size.height);
+      overlap_x >= MIN_VISIBLE_WINDOW_AREA && overlap_y >= MIN_VISIBLE_WINDOW_AREA
+    });
+    if !monitors.is_empty() && !visible {
+      warn!("ウィンドウ位置 ({}, {}) が画面外のためデフォルト位置に戻しました", state.x, state.y);
+      state.x = defaults.window_state.x;
+      state.y = defaults.window_state.y;
+    }
+  }
+  store.set("window_state", json!(state));
+  Ok(())
 }
 
-/// ウィンドウ状態を読み込み
-pub fn load_window_state(app: &AppHandle, config_dir: &PathBuf) -> Result<WindowState, Box<dyn std::error::Error>> {
-  let path = config_dir.join("D4CleaningStudio.config");
-  let store = app.store(path.to_string_lossy().as_ref())?;
-  let st = match store.get("window_state") {
-    Some(v) => serde_json::from_value(v.clone())?,
-    None => return Err("window_state が存在しません".into()),
+/// 設定ファイルを現在のスキーマバージョンへ移行
+/// ストアの内容に `migrate_entries` を適用し、変わったキーだけを書き戻す
+fn migrate_store(store: &Store<Wry>) -> Result<(), Box<dyn std::error::Error>> {
+  let before: serde_json::Map<String, Value> = store.entries().into_iter().collect();
+  let mut entries = before.clone();
+  if !migrate_entries(&mut entries)? {
+    return Ok(());
+  }
+
+  for key in before.keys().filter(|key| !entries.contains_key(*key)) {
+    store.delete(key);
+  }
+  for (key, value) in entries {
+    if before.get(&key) != Some(&value) {
+      store.set(key, value);
+    }
+  }
+  Ok(())
+}
+
+/// 設定の内容を現在のスキーマバージョンへ移行
+/// 1. `schema_version` より新しい移行処理を順に適用
+/// 2. 構造体に追加されたフィールドをデフォルト値で補完（デシリアライズ失敗を防ぐ）
+/// `schema_version` がこのバージョンより新しい場合は何も変更せず false を返す
+fn migrate_entries(entries: &mut serde_json::Map<String, Value>) -> Result<bool, Box<dyn std::error::Error>> {
+  // schema_version が無い設定はバージョン導入前（0）とみなす
+  let version = entries.get("schema_version").and_then(|v| v.as_u64()).unwrap_or(0);
+  if version > CURRENT_SCHEMA_VERSION {
+    warn!("設定ファイルのスキーマバージョン {} はこのバージョンより新しいため移行しません", version);
+    return Ok(false);
+  }
+
+  for (target, migration) in MIGRATIONS.iter().filter(|(target, _)| *target > version) {
+    migration(entries);
+    info!("設定ファイルをスキーマバージョン {} へ移行しました", target);
+  }
+
+  // 既存キーの不足フィールドをデフォルト値で補完
+  if let Value::Object(defaults) = serde_json::to_value(Config::default())? {
+    for (key, default) in defaults.iter() {
+      if let Some(current) = entries.get_mut(key) {
+        if fill_missing_fields(current, default) {
+          info!("{} に不足していたフィールドをデフォルト値で補完しました", key);
+        }
+      }
+    }
+  }
+
+  entries.insert(String::from("schema_version"), json!(CURRENT_SCHEMA_VERSION));
+  Ok(true)
+}
+
+/// `default` にあって `current` に無いフィールドを再帰的に補う
+/// 何か補った場合は true を返す
+fn fill_missing_fields(current: &mut Value, default: &Value) -> bool {
+  let (Value::Object(current), Value::Object(default)) = (current, default) else {
+    return false;
   };
-  info!("ウィンドウ状態を読み込みました: {:?}", st);
+
+  let mut changed = false;
+  for (key, default_value) in default {
+    match current.get_mut(key) {
+      Some(value) => changed |= fill_missing_fields(value, default_value),
+      None => {
+        current.insert(key.clone(), default_value.clone());
+        changed = true;
+      },
+    }
+  }
+  changed
+}
+
+/// スキーマバージョン 1 への移行
+/// バージョン導入前の project_config を従来の単一オブジェクト形式として補完し、
+/// プロジェクト一覧（projects）が無ければ project_config を引き継いで作成する
+fn migrate_to_v1(entries: &mut serde_json::Map<String, Value>) {
+  let legacy = entries.get_mut("project_config").map(|project| {
+    fill_missing_fields(project, &json!(ProjectConfig::default()));
+    project.clone()
+  });
+  if !entries.contains_key("projects") {
+    let projects: Vec<ProjectConfig> = legacy
+      .and_then(|v| serde_json::from_value::<ProjectConfig>(v).ok())
+      .filter(|p| !p.name.trim().is_empty())
+      .into_iter()
+      .collect();
+    entries.insert(String::from("projects"), json!(projects));
+  }
+}
+
+/// プロジェクト設定を読み込み
+pub fn load_project_config(app: &AppHandle, config_dir: &PathBuf) -> Result<ProjectConfig, Box<dyn std::error::Error>> {
+  let cfg = shared_store(app, config_dir)?.project_config()?;
+  info!("プロジェクト設定を読み込みました: {:?}", cfg);
+  Ok(cfg)
+}
+
+/// ウィンドウ基本設定を読み込み
+pub fn load_window_config(app: &AppHandle, config_dir: &PathBuf) -> Result<WindowConfig, Box<dyn std::error::Error>> {
+  let cfg = shared_store(app, config_dir)?.window_config()?;
+  info!("ウィンドウ設定を読み込みました: {:?}", cfg);
+  Ok(cfg)
+}
+
+/// ウィンドウ状態を読み込み
+pub fn load_window_state(app: &AppHandle, config_dir: &PathBuf) -> Result<WindowState, Box<dyn std::error::Error>> {
+  let st = shared_store(app, config_dir)?.window_state()?;
+  info!("ウィンドウ状態を読み込みました: {:?}", st);
   Ok(st)
 }
+
+/// パフォーマンスモードを読み込み
+pub fn load_performance_mode(app: &AppHandle, config_dir: &PathBuf) -> Result<PerformanceMode, Box<dyn std::error::Error>> {
+  let store = shared_store(app, config_dir)?;
+  let mode = match store.get("performance_mode") {
+    Some(v) => serde_json::from_value(v.clone())?,
+    None => return Err("performance_mode が存在しません".into()),
+  };
+  info!("パフォーマンスモードを読み込みました: {:?}", mode);
+  Ok(mode)
+}
+
+/// パフォーマンスモードを保存
+pub fn save_performance_mode(app: &AppHandle, config_dir: &PathBuf, mode: PerformanceMode) -> Result<(), Box<dyn std::error::Error>> {
+  with_store(app, config_dir, |tx| {
+    tx.set("performance_mode", json!(mode));
+    Ok(())
+  })?;
+  info!("パフォーマンスモードを保存しました: {:?}", mode);
+  Ok(())
+}
+
+/// レイアウトプリセット一覧を読み込み
+pub fn load_layout_presets(app: &AppHandle, config_dir: &PathBuf) -> Result<Vec<LayoutPreset>, Box<dyn std::error::Error>> {
+  Ok(read_key(&shared_store(app, config_dir)?, "layout_presets")?.unwrap_or_default())
+}
+
+/// 現在のメインパネルレイアウトを名前付きプリセットとして保存
+/// 同名のプリセットが存在する場合は上書きする
+pub fn save_layout_preset(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<Vec<LayoutPreset>, Box<dyn std::error::Error>> {
+  let presets = with_store(app, config_dir, |tx| {
+    let state: WindowState = read_key(tx, "window_state")?.ok_or("window_state が存在しません")?;
+    let mut presets: Vec<LayoutPreset> = read_key(tx, "layout_presets")?.unwrap_or_default();
+    match presets.iter_mut().find(|p| p.name == name) {
+      Some(preset) => preset.layout = state.main_panel_layout,
+      None => presets.push(LayoutPreset {
+        name: name.to_string(),
+        layout: state.main_panel_layout,
+      }),
+    }
+    tx.set("layout_presets", json!(presets));
+    Ok(presets)
+  })?;
+  info!("レイアウトプリセットを保存しました: {}", name);
+  Ok(presets)
+}
+
+/// 名前付きプリセットをメインパネルレイアウトへ適用
+/// 適用後のレイアウトを window_state に保存して返す
+pub fn apply_layout_preset(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<MainPanelLayout, Box<dyn std::error::Error>> {
+  let layout = with_store(app, config_dir, |tx| {
+    let presets: Vec<LayoutPreset> = read_key(tx, "layout_presets")?.unwrap_or_default();
+    let preset = match presets.into_iter().find(|p| p.name == name) {
+      Some(p) => p,
+      None => return Err(format!("レイアウトプリセット '{}' が存在しません", name).into()),
+    };
+    let mut state: WindowState = read_key(tx, "window_state")?.ok_or("window_state が存在しません")?;
+    state.main_panel_layout = preset.layout.clone();
+    tx.set("window_state", json!(state));
+    Ok(preset.layout)
+  })?;
+  info!("レイアウトプリセットを適用しました: {}", name);
+  Ok(layout)
+}
+
+/// 名前付きプリセットを削除
+pub fn delete_layout_preset(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<Vec<LayoutPreset>, Box<dyn std::error::Error>> {
+  let presets = with_store(app, config_dir, |tx| {
+    let mut presets: Vec<LayoutPreset> = read_key(tx, "layout_presets")?.unwrap_or_default();
+    let before = presets.len();
+    presets.retain(|p| p.name != name);
+    if presets.len() == before {
+      return Err(format!("レイアウトプリセット '{}' が存在しません", name).into());
+    }
+    tx.set("layout_presets", json!(presets));
+    Ok(presets)
+  })?;
+  info!("レイアウトプリセットを削除しました: {}", name);
+  Ok(presets)
+}
+
+/// 列構成からスキーマハッシュを計算
+/// 列名を順序込みで FNV-1a (64bit) にかけ、16進文字列で返す
+/// （永続化のキーに使うため、Rust のバージョンで変わらないハッシュを自前で計算する）
+pub fn schema_hash(columns: &[String]) -> String {
+  let hash = columns.iter().fold(0xcbf2_9ce4_8422_2325, |hash, column| fnv1a(fnv1a(hash, column.as_bytes()), &[0x1f]));
+  format!("{:016x}", hash)
+}
+
+/// FNV-1a (64bit) のハッシュ値 `hash` に `bytes` を追加する
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+  bytes.iter().fold(hash, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// 列構成に対応する列表示設定を読み込み
+/// 未保存の列構成の場合は None を返す
+pub fn load_column_preferences(app: &AppHandle, config_dir: &PathBuf, columns: &[String]) -> Result<Option<ColumnDisplayPreferences>, Box<dyn std::error::Error>> {
+  let mut all: HashMap<String, ColumnDisplayPreferences> = read_key(&shared_store(app, config_dir)?, "column_preferences")?.unwrap_or_default();
+  Ok(all.remove(&schema_hash(columns)))
+}
+
+/// 列構成に対応する列表示設定を保存
+pub fn save_column_preferences(app: &AppHandle, config_dir: &PathBuf, columns: &[String], preferences: ColumnDisplayPreferences) -> Result<(), Box<dyn std::error::Error>> {
+  let hash = schema_hash(columns);
+  with_store(app, config_dir, |tx| {
+    let mut all: HashMap<String, ColumnDisplayPreferences> = read_key(tx, "column_preferences")?.unwrap_or_default();
+    all.insert(hash.clone(), preferences);
+    tx.set("column_preferences", json!(all));
+    Ok(())
+  })?;
+  info!("列表示設定を保存しました: {}", hash);
+  Ok(())
+}
+
+/// プロジェクト一覧を読み込み
+pub fn load_projects(app: &AppHandle, config_dir: &PathBuf) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  Ok(read_key(&shared_store(app, config_dir)?, "projects")?.unwrap_or_default())
+}
+
+/// RFC 3339 の日時を UTC に変換（読み込めない・空の場合は None）
+/// 以前のバージョンはローカル時刻で記録していたため、文字列ではなく時刻として比較する
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+  DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+/// プロジェクト一覧を指定した順序で並べ替え
+pub fn sort_projects(projects: &mut [ProjectConfig], sort: ProjectSort) {
+  match sort {
+    ProjectSort::Name => projects.sort_by(|a, b| a.name.cmp(&b.name)),
+    ProjectSort::CreatedAt => projects.sort_by_key(|p| std::cmp::Reverse(parse_timestamp(&p.created_at))),
+    ProjectSort::LastOpenedAt => projects.sort_by_key(|p| std::cmp::Reverse(p.last_opened_at.as_deref().and_then(parse_timestamp))),
+  }
+}
+
+/// ピン留めしたプロジェクトを先頭へ移動（それぞれの中の並び順は維持）
+pub fn pinned_first(projects: &mut [ProjectConfig]) {
+  projects.sort_by_key(|p| !p.pinned);
+}
+
+/// プロジェクトのピン留めを設定・解除
+pub fn set_project_pinned(app: &AppHandle, config_dir: &PathBuf, name: &str, pinned: bool) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  let projects = update_projects(app, config_dir, |projects| {
+    match projects.iter_mut().find(|p| p.name == name) {
+      Some(project) => project.pinned = pinned,
+      None => return Err(format!("プロジェクト '{}' が存在しません", name).into()),
+    }
+    Ok(())
+  })?;
+  info!("プロジェクト '{}' のピン留めを{}しました", name, if pinned { "設定" } else { "解除" });
+  Ok(projects)
+}
+
+/// プロジェクトの説明・タグを更新
+/// None の項目は変更しない
+pub fn update_project_metadata(app: &AppHandle, config_dir: &PathBuf, name: &str, description: Option<String>, tags: Option<Vec<String>>) -> Result<ProjectConfig, Box<dyn std::error::Error>> {
+  let mut updated = None;
+  update_projects(app, config_dir, |projects| {
+    let project = match projects.iter_mut().find(|p| p.name == name) {
+      Some(p) => p,
+      None => return Err(format!("プロジェクト '{}' が存在しません", name).into()),
+    };
+    if let Some(description) = description {
+      project.description = description;
+    }
+    if let Some(tags) = tags {
+      let mut unique: Vec<String> = Vec::new();
+      for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !unique.iter().any(|u| u == tag) {
+          unique.push(tag.to_string());
+        }
+      }
+      project.tags = unique;
+    }
+    updated = Some(project.clone());
+    Ok(())
+  })?;
+  info!("プロジェクトのメタデータを更新しました: {}", name);
+  updated.ok_or_else(|| format!("プロジェクト '{}' が存在しません", name).into())
+}
+
+/// プロジェクト一覧を読み込み→変更→保存する
+/// 読み込みから保存までを `with_store` のロック内で行う
+fn update_projects(app: &AppHandle, config_dir: &PathBuf, f: impl FnOnce(&mut Vec<ProjectConfig>) -> Result<(), Box<dyn std::error::Error>>) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  with_store(app, config_dir, |tx| {
+    let mut projects: Vec<ProjectConfig> = read_key(tx, "projects")?.unwrap_or_default();
+    f(&mut projects)?;
+    tx.set("projects", json!(projects));
+    Ok(projects)
+  })
+}
+
+/// プロジェクトを一覧に追加
+/// プロジェクト名は一覧内で一意とする
+pub fn add_project(app: &AppHandle, config_dir: &PathBuf, project: ProjectConfig) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  if project.name.trim().is_empty() {
+    return Err("プロジェクト名が空です".into());
+  }
+  safe_opener::validate_root(&project.filepath)?;
+  let mut project = project;
+  if project.created_at.is_empty() {
+    project.created_at = Utc::now().to_rfc3339();
+  }
+
+  let name = project.name.clone();
+  let projects = update_projects(app, config_dir, |projects| {
+    if projects.iter().any(|p| p.name == project.name) {
+      return Err(format!("プロジェクト '{}' は既に存在します", project.name).into());
+    }
+    projects.push(project);
+    Ok(())
+  })?;
+  info!("プロジェクトを追加しました: {}", name);
+  Ok(projects)
+}
+
+/// 一覧内のプロジェクトを更新
+/// `name` で対象を特定し、名前の変更も許可する（変更後の名前が重複する場合はエラー）
+/// 名前を変更した場合はワークスペースの開いているプロジェクトも新しい名前に置き換える
+/// 最近開いた一覧・現在のプロジェクト（project_config）の該当エントリも新しい名前・パスに合わせる
+/// 更新するのは名前・パス・備考のみで、作成日時・最後に開いた日時・説明・タグ・ピン留めは登録済みの値を残す
+/// （説明・タグは `update_project_metadata`、ピン留めは `set_project_pinned` で変更する）
+pub fn update_project(app: &AppHandle, config_dir: &PathBuf, name: &str, project: ProjectConfig) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  if project.name.trim().is_empty() {
+    return Err("プロジェクト名が空です".into());
+  }
+  safe_opener::validate_root(&project.filepath)?;
+  let projects = with_store(app, config_dir, |tx| {
+    let mut projects: Vec<ProjectConfig> = read_key(tx, "projects")?.unwrap_or_default();
+    if project.name != name && projects.iter().any(|p| p.name == project.name) {
+      return Err(format!("プロジェクト '{}' は既に存在します", project.name).into());
+    }
+    let previous = match projects.iter_mut().find(|p| p.name == name) {
+      Some(entry) => {
+        let previous = entry.clone();
+        entry.name = project.name.clone();
+        entry.filepath = project.filepath;
+        entry.remarks = project.remarks;
+        previous
+      },
+      None => return Err(format!("プロジェクト '{}' が存在しません", name).into()),
+    };
+    let updated = projects.iter().find(|p| p.name == project.name).cloned();
+    tx.set("projects", json!(projects));
+    if project.name != name {
+      rename_in_workspace(tx, name, Some(&project.name))?;
+    }
+    retarget_project_refs(tx, &previous, updated.as_ref())?;
+    Ok(projects)
+  })?;
+  info!("プロジェクトを更新しました: {}", name);
+  Ok(projects)
+}
+
+/// プロジェクトを一覧から削除
+/// 一覧から外すだけで、プロジェクトフォルダ自体は削除しない
+/// ワークスペースで開いている場合は閉じ、最近開いた一覧・現在のプロジェクトからも外す
+pub fn remove_project(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  let projects = with_store(app, config_dir, |tx| {
+    let mut projects: Vec<ProjectConfig> = read_key(tx, "projects")?.unwrap_or_default();
+    let removed = match projects.iter().position(|p| p.name == name) {
+      Some(index) => projects.remove(index),
+      None => return Err(format!("プロジェクト '{}' が存在しません", name).into()),
+    };
+    tx.set("projects", json!(projects));
+    rename_in_workspace(tx, name, None)?;
+    retarget_project_refs(tx, &removed, None)?;
+    Ok(projects)
+  })?;
+  info!("プロジェクトを削除しました: {}", name);
+  Ok(projects)
+}
+
+/// `with_store` のクロージャ内で、ワークスペースが保持するプロジェクト名を変更する（`to` が None の場合は閉じる）
+/// ワークスペースはプロジェクト名で保持しているため、プロジェクトの名前変更・削除に追従させる
+fn rename_in_workspace(tx: &Store<Wry>, from: &str, to: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+  let mut workspace: Workspace = read_key(tx, "workspace")?.unwrap_or_default();
+  match to {
+    Some(to) => {
+      for open in workspace.open_projects.iter_mut().filter(|n| n.as_str() == from) {
+        *open = to.to_string();
+      }
+      if workspace.active.as_deref() == Some(from) {
+        workspace.active = Some(to.to_string());
+      }
+    },
+    None => {
+      workspace.open_projects.retain(|n| n != from);
+      if workspace.active.as_deref() == Some(from) {
+        workspace.active = workspace.open_projects.last().cloned();
+      }
+    },
+  }
+  tx.set("workspace", json!(workspace));
+  Ok(())
+}
+
+/// 最近開いた一覧のうち `from` のパスのエントリを `to`（名前, パス）に置き換える（`to` が None の場合は削除）
+/// 置き換え先のパスが既に一覧にある場合は、新しい方（先頭に近い方）だけを残す
+fn retarget_recent(recent: &mut Vec<RecentProject>, from: &str, to: Option<(&str, &str)>) {
+  match to {
+    Some((name, filepath)) => {
+      for entry in recent.iter_mut().filter(|r| r.filepath == from) {
+        entry.name = name.to_string();
+        entry.filepath = filepath.to_string();
+      }
+      let mut seen = false;
+      recent.retain(|r| r.filepath != filepath || !std::mem::replace(&mut seen, true));
+    },
+    None => recent.retain(|r| r.filepath != from),
+  }
+}
+
+/// `with_store` のクロージャ内で、最近開いた一覧と現在のプロジェクト（project_config）を登録済みプロジェクトの変更に追従させる
+/// `updated` が None の場合は削除として扱い、現在のプロジェクトだった場合は未選択に戻す
+fn retarget_project_refs(tx: &Store<Wry>, previous: &ProjectConfig, updated: Option<&ProjectConfig>) -> Result<(), Box<dyn std::error::Error>> {
+  let mut recent: Vec<RecentProject> = read_key(tx, "recent_projects")?.unwrap_or_default();
+  retarget_recent(&mut recent, &previous.filepath, updated.map(|p| (p.name.as_str(), p.filepath.as_str())));
+  tx.set("recent_projects", json!(recent));
+
+  let current: Option<ProjectConfig> = read_key(tx, "project_config")?;
+  if let Some(mut current) = current.filter(|c| c.name == previous.name && c.filepath == previous.filepath) {
+    match updated {
+      Some(project) => {
+        current.name = project.name.clone();
+        current.filepath = project.filepath.clone();
+        current.remarks = project.remarks.clone();
+      },
+      None => current = ProjectConfig::default(),
+    }
+    tx.set("project_config", json!(current));
+  }
+  Ok(())
+}
+
+/// 最近開いたプロジェクト一覧を読み込み（新しい順）
+pub fn load_recent_projects(app: &AppHandle, config_dir: &PathBuf) -> Result<Vec<RecentProject>, Box<dyn std::error::Error>> {
+  Ok(read_key(&shared_store(app, config_dir)?, "recent_projects")?.unwrap_or_default())
+}
+
+/// プロジェクトを最近開いた一覧の先頭に追加
+/// 同じパスのエントリは先頭へ移動し、最大件数を超えた古いエントリは削除する
+pub fn push_recent_project(app: &AppHandle, config_dir: &PathBuf, name: &str, filepath: &str) -> Result<Vec<RecentProject>, Box<dyn std::error::Error>> {
+  let recent = with_store(app, config_dir, |tx| push_recent_in(tx, name, filepath))?;
+  info!("最近開いたプロジェクトに追加しました: {}", filepath);
+  Ok(recent)
+}
+
+/// `with_store` のクロージャ内で、プロジェクトを最近開いた一覧の先頭に追加する
+/// 登録済みプロジェクトであれば最後に開いた日時も更新する
+fn push_recent_in(tx: &Store<Wry>, name: &str, filepath: &str) -> Result<Vec<RecentProject>, Box<dyn std::error::Error>> {
+  let now = Utc::now().to_rfc3339();
+  let mut recent: Vec<RecentProject> = read_key(tx, "recent_projects")?.unwrap_or_default();
+  recent.retain(|r| r.filepath != filepath);
+  recent.insert(
+    0,
+    RecentProject {
+      name: name.to_string(),
+      filepath: filepath.to_string(),
+      opened_at: now.clone(),
+    },
+  );
+  recent.truncate(MAX_RECENT_PROJECTS);
+  tx.set("recent_projects", json!(recent));
+
+  let mut projects: Vec<ProjectConfig> = read_key(tx, "projects")?.unwrap_or_default();
+  if let Some(project) = projects.iter_mut().find(|p| p.filepath == filepath) {
+    project.last_opened_at = Some(now);
+    tx.set("projects", json!(projects));
+  }
+  Ok(recent)
+}
+
+/// 最近開いた一覧のプロジェクトを開く
+/// 現在のプロジェクト（project_config）を切り替え、一覧の先頭へ移動する
+/// プロジェクト一覧に登録済みであれば、その設定（備考など）を引き継ぐ
+pub fn open_recent_project(app: &AppHandle, config_dir: &PathBuf, filepath: &str) -> Result<ProjectConfig, Box<dyn std::error::Error>> {
+  let project = with_store(app, config_dir, |tx| {
+    let recent: Vec<RecentProject> = read_key(tx, "recent_projects")?.unwrap_or_default();
+    let entry = match recent.into_iter().find(|r| r.filepath == filepath) {
+      Some(r) => r,
+      None => return Err(format!("最近開いたプロジェクトに '{}' が存在しません", filepath).into()),
+    };
+    push_recent_in(tx, &entry.name, &entry.filepath)?;
+
+    let projects: Vec<ProjectConfig> = read_key(tx, "projects")?.unwrap_or_default();
+    let project = projects.into_iter().find(|p| p.filepath == filepath).unwrap_or_else(|| ProjectConfig {
+      name: entry.name,
+      filepath: entry.filepath,
+      ..Default::default()
+    });
+    tx.set("project_config", json!(project));
+    Ok(project)
+  })?;
+
+  info!("最近開いたプロジェクトを開きました: {}", filepath);
+  Ok(project)
+}
+
+/// 最近開いたプロジェクト一覧をクリア
+pub fn clear_recent_projects(app: &AppHandle, config_dir: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+  with_store(app, config_dir, |tx| {
+    tx.set("recent_projects", json!(Vec::<RecentProject>::new()));
+    Ok(())
+  })?;
+  info!("最近開いたプロジェクト一覧をクリアしました");
+  Ok(())
+}
+
+/// ウィンドウ状態を保存
+pub fn save_window_state(app: &AppHandle, config_dir: &PathBuf, state: &WindowState) -> Result<(), Box<dyn std::error::Error>> {
+  with_store(app, config_dir, |tx| {
+    tx.set("window_state", json!(state));
+    Ok(())
+  })?;
+  info!("ウィンドウ状態を保存しました: {:?}", state);
+  Ok(())
+}
+
+/// 通常表示時のウィンドウサイズ・位置を記録
+/// `Moved` / `Resized` イベントから呼び出す（最大化・最小化中は記録しない）
+pub fn track_window_bounds(window: &Window) {
+  let (Ok(false), Ok(false)) = (window.is_maximized(), window.is_minimized()) else {
+    return;
+  };
+  if let (Ok(size), Ok(position)) = (window.inner_size(), window.outer_position()) {
+    if let Ok(mut bounds) = NORMAL_WINDOW_BOUNDS.lock() {
+      *bounds = Some((size, position));
+    }
+  }
+}
+
+/// ウィンドウの現在の状態を `base` に反映した WindowState を作成
+/// 最大化・最小化中は、記録済みの通常表示時のサイズ・位置を使う
+/// テーマは組み込みの Light / Dark が設定されている場合のみウィンドウの実際のテーマで更新する
+/// （"auto" やカスタムテーマは利用者の選択をそのまま残す）
+pub fn capture_window_state(window: &Window, base: &WindowState) -> Result<WindowState, Box<dyn std::error::Error>> {
+  let mut state = base.clone();
+  state.fullscreen = window.is_maximized()?;
+
+  let bounds = if !state.fullscreen && !window.is_minimized()? {
+    Some((window.inner_size()?, window.outer_position()?))
+  } else {
+    NORMAL_WINDOW_BOUNDS.lock().ok().and_then(|b| *b)
+  };
+  if let Some((size, position)) = bounds {
+    state.width = size.width;
+    state.height = size.height;
+    state.x = position.x;
+    state.y = position.y;
+  }
+
+  if matches!(state.theme.as_str(), "Light" | "Dark") {
+    state.theme = match window.theme()? {
+      tauri::Theme::Dark => String::from("Dark"),
+      _ => String::from("Light"),
+    };
+  }
+  Ok(state)
+}
+
+/// ウィンドウの現在の状態を取得して保存
+/// パネルレイアウトはフロントエンドが保存した値（または `main_panel_layout`）を使う
+pub fn persist_window_state(app: &AppHandle, config_dir: &PathBuf, window: &Window, main_panel_layout: Option<MainPanelLayout>) -> Result<WindowState, Box<dyn std::error::Error>> {
+  let state = with_store(app, config_dir, |tx| {
+    let mut base: WindowState = read_key(tx, "window_state")?.ok_or("window_state が存在しません")?;
+    if let Some(layout) = main_panel_layout {
+      base.main_panel_layout = layout;
+    }
+    let state = capture_window_state(window, &base)?;
+    tx.set("window_state", json!(state));
+    Ok(state)
+  })?;
+  info!("ウィンドウ状態を保存しました: {:?}", state);
+  Ok(state)
+}
+
+/// 書き出し・読み込みの対象外とするキー
+/// シークレットはマシン固有の鍵で暗号化しているため、書き出しても別のマシンでは復号できない
+const EXPORT_LOCAL_KEYS: &[&str] = &["secrets"];
+
+/// 設定全体をポータブルな JSON ファイルへ書き出す
+/// ストアのキーを `schema_version` ごとそのまま出力する（`EXPORT_LOCAL_KEYS` は除く）
+pub fn export_settings(app: &AppHandle, config_dir: &PathBuf, target: &Path) -> Result<(), Box<dyn std::error::Error>> {
+  let store = shared_store(app, config_dir)?;
+  let entries: serde_json::Map<String, Value> = store.entries().into_iter().filter(|(key, _)| !EXPORT_LOCAL_KEYS.contains(&key.as_str())).collect();
+  write_access::check_writable(target)?;
+  fs::write(path_utils::for_io(target), serde_json::to_vec_pretty(&entries)?)?;
+  info!("設定をエクスポートしました: {}", target.display());
+  Ok(())
+}
+
+/// 書き出した設定ファイルを検証して読み込む
+/// 不足フィールドはデフォルト値で補ったうえで、既知のキーがすべて正しい型か確認してから置き換える
+/// 検証に失敗した場合、現在の設定は変更しない
+/// `EXPORT_LOCAL_KEYS`（シークレット）はファイルの内容に関係なくこのマシンの値を残す
+///
+/// # 戻り値
+/// * 読み込んだキーの一覧
+pub fn import_settings(app: &AppHandle, config_dir: &PathBuf, source: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+  import_settings_keeping(app, config_dir, source, EXPORT_LOCAL_KEYS)
+}
+
+/// 書き出した設定ファイルを読み込む
+/// `keep` に指定したキーはファイルの内容に関係なく現在の値を残す
+fn import_settings_keeping(app: &AppHandle, config_dir: &PathBuf, source: &Path, keep: &[&str]) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+  let mut entries = read_settings_file(source)?;
+  entries.retain(|key, _| !keep.contains(&key.as_str()));
+  let keys = replace_settings(app, config_dir, entries, keep)?;
+  info!("設定をインポートしました: {} ({:?})", source.display(), keys);
+  Ok(keys)
+}
+
+/// 書き出した設定ファイルを JSON オブジェクトとして読み込む
+fn read_settings_file(source: &Path) -> Result<serde_json::Map<String, Value>, Box<dyn std::error::Error>> {
+  match serde_json::from_slice::<Value>(&fs::read(path_utils::for_io(source))?)? {
+    Value::Object(map) => Ok(map),
+    _ => Err("設定ファイルの形式が正しくありません（JSON オブジェクトではありません）".into()),
+  }
+}
+
+/// ストアの内容を読み込んだ設定で置き換える
+/// 読み込んだ設定に無いキーや不足フィールドはデフォルト値で補い、範囲外の値は保存前に修復する
+/// `keep` に指定したキーは読み込んだ設定に関係なく現在の値を残す（マシン固有の設定など）
+fn replace_settings(app: &AppHandle, config_dir: &PathBuf, entries: serde_json::Map<String, Value>, keep: &[&str]) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+  let version = entries.get("schema_version").and_then(|v| v.as_u64()).unwrap_or(0);
+  if version > CURRENT_SCHEMA_VERSION {
+    return Err(format!("設定ファイルのスキーマバージョン {} はこのバージョンでは読み込めません", version).into());
+  }
+  let keys: Vec<String> = entries.keys().filter(|key| !keep.contains(&key.as_str())).cloned().collect();
+
+  // デフォルト設定に読み込んだ設定を重ね、不足しているキー・フィールドを補完
+  let mut merged = match serde_json::to_value(Config::default())? {
+    Value::Object(map) => map,
+    _ => return Err("デフォルト設定の生成に失敗しました".into()),
+  };
+  merged.insert(String::from("project_config"), json!(ProjectConfig::default()));
+  for (key, mut value) in entries {
+    if let Some(default) = merged.get(&key) {
+      fill_missing_fields(&mut value, default);
+    }
+    merged.insert(key, value);
+  }
+  validate_settings(&merged)?;
+
+  with_store(app, config_dir, |tx| {
+    let kept: Vec<(String, Value)> = keep.iter().filter_map(|key| tx.get(*key).map(|value| (key.to_string(), value))).collect();
+    tx.clear();
+    for (key, value) in merged {
+      tx.set(key, value);
+    }
+    for (key, value) in kept {
+      tx.set(key, value);
+    }
+    migrate_store(tx)?;
+    repair_store(app, tx)
+  })?;
+  Ok(keys)
+}
+
+/// 既知のキーが対応する型として読み込めるか検証
+fn validate_settings(entries: &serde_json::Map<String, Value>) -> Result<(), Box<dyn std::error::Error>> {
+  fn check<T: serde::de::DeserializeOwned>(entries: &serde_json::Map<String, Value>, key: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(value) = entries.get(key) {
+      serde_json::from_value::<T>(value.clone()).map_err(|e| format!("{} の形式が正しくありません: {}", key, e))?;
+    }
+    Ok(())
+  }
+
+  check::<ProjectConfig>(entries, "project_config")?;
+  check::<Vec<ProjectConfig>>(entries, "projects")?;
+  check::<Vec<RecentProject>>(entries, "recent_projects")?;
+  check::<Workspace>(entries, "workspace")?;
+  check::<DialogDirectories>(entries, "dialog_directories")?;
+  check::<WindowConfig>(entries, "window_config")?;
+  check::<WindowState>(entries, "window_state")?;
+  check::<PerformanceMode>(entries, "performance_mode")?;
+  check::<Vec<LayoutPreset>>(entries, "layout_presets")?;
+  check::<Vec<Keybinding>>(entries, "keybindings")?;
+  check::<Vec<CustomTheme>>(entries, "custom_themes")?;
+  check::<String>(entries, "active_profile")?;
+  check::<SettingsSync>(entries, "settings_sync")?;
+  check::<HashMap<String, ColumnDisplayPreferences>>(entries, "column_preferences")?;
+  check::<HashMap<String, EncryptedSecret>>(entries, "secrets")?;
+
+  // プロジェクトフォルダ・エクスポート先はファイルを開くことを許可するフォルダになるため、範囲を検証する
+  if let Some(projects) = entries.get("projects") {
+    for project in serde_json::from_value::<Vec<ProjectConfig>>(projects.clone())? {
+      safe_opener::validate_root(&project.filepath)?;
+    }
+  }
+  if let Some(dirs) = entries.get("dialog_directories") {
+    validate_export_roots(&serde_json::from_value(dirs.clone())?)?;
+  }
+  Ok(())
+}
+
+/// エクスポートの既定・最後に使ったフォルダが、ファイルを開くことを許可するフォルダとして使えるか検証
+fn validate_export_roots(dirs: &DialogDirectories) -> Result<(), Box<dyn std::error::Error>> {
+  for root in [dirs.defaults.get(&DialogCategory::Export), dirs.last_used.get(&DialogCategory::Export)].into_iter().flatten() {
+    safe_opener::validate_root(root)?;
+  }
+  Ok(())
+}
+
+/// 全体のリセット（`ResetScope::All`）でデフォルト値に戻す設定のキー（`project_config` を除く）
+/// プロジェクト一覧・最近開いたプロジェクト・ワークスペース・カスタムテーマ・同期設定・シークレット・
+/// 列表示設定は利用者のデータのため、全体のリセットでも残す
+const RESET_ALL_KEYS: &[&str] = &["window_state", "window_config", "dialog_directories", "performance_mode", "layout_presets", "keybindings"];
+
+/// リセット範囲に含まれるキーとデフォルト値
+fn reset_targets(scope: ResetScope) -> Result<Vec<(String, Value)>, Box<dyn std::error::Error>> {
+  let defaults = match serde_json::to_value(Config::default())? {
+    Value::Object(map) => map,
+    _ => return Err("デフォルト設定の生成に失敗しました".into()),
+  };
+  let keys: &[&str] = match scope {
+    ResetScope::WindowState => &["window_state"],
+    ResetScope::WindowConfig => &["window_config"],
+    ResetScope::ProjectConfig => &[],
+    ResetScope::All => RESET_ALL_KEYS,
+  };
+  let mut targets: Vec<(String, Value)> = keys.iter().filter_map(|key| defaults.get(*key).map(|value| (key.to_string(), value.clone()))).collect();
+  if matches!(scope, ResetScope::ProjectConfig | ResetScope::All) {
+    targets.push((String::from("project_config"), json!(ProjectConfig::default())));
+  }
+  Ok(targets)
+}
+
+/// 指定した範囲の設定をデフォルト値に戻す
+/// 対象キーだけを `Config::default()`（`project_config` は `ProjectConfig::default()`）で上書きし、
+/// UI が再読み込みできるよう `settings-reset` イベントを送信する
+/// 全体のリセットでも利用者のデータ（`RESET_ALL_KEYS` 以外のキー）は残す
+///
+/// # 戻り値
+/// * デフォルト値に戻したキーの一覧
+pub fn reset_settings(app: &AppHandle, config_dir: &PathBuf, scope: ResetScope) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+  let targets = reset_targets(scope)?;
+  let keys: Vec<String> = targets.iter().map(|(key, _)| key.clone()).collect();
+  with_store(app, config_dir, |tx| {
+    for (key, value) in targets {
+      tx.set(key, value);
+    }
+    Ok(())
+  })?;
+  info!("設定をデフォルト値に戻しました: {:?} ({:?})", scope, keys);
+
+  let payload = SettingsResetPayload { scope, keys: keys.clone() };
+  if let Err(e) = app.emit("settings-reset", payload) {
+    error!("settings-reset イベントの送信に失敗しました: {}", e);
+  }
+  Ok(keys)
+}
+
+/// プロジェクトに付いているタグを集計（タグ名順）
+pub fn list_tags(app: &AppHandle, config_dir: &PathBuf) -> Result<Vec<TagSummary>, Box<dyn std::error::Error>> {
+  let mut counts: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+  for project in load_projects(app, config_dir)? {
+    for tag in project.tags {
+      *counts.entry(tag).or_insert(0) += 1;
+    }
+  }
+  Ok(counts.into_iter().map(|(tag, count)| TagSummary { tag, count }).collect())
+}
+
+/// プロジェクトにタグを付ける（付与済みなら何もしない）
+pub fn add_project_tag(app: &AppHandle, config_dir: &PathBuf, name: &str, tag: &str) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  let tag = tag.trim();
+  if tag.is_empty() {
+    return Err("タグが空です".into());
+  }
+  let projects = update_projects(app, config_dir, |projects| {
+    let project = match projects.iter_mut().find(|p| p.name == name) {
+      Some(p) => p,
+      None => return Err(format!("プロジェクト '{}' が存在しません", name).into()),
+    };
+    if !project.tags.iter().any(|t| t == tag) {
+      project.tags.push(tag.to_string());
+    }
+    Ok(())
+  })?;
+  info!("プロジェクト '{}' にタグ '{}' を付けました", name, tag);
+  Ok(projects)
+}
+
+/// プロジェクトからタグを外す
+pub fn remove_project_tag(app: &AppHandle, config_dir: &PathBuf, name: &str, tag: &str) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  let projects = update_projects(app, config_dir, |projects| {
+    match projects.iter_mut().find(|p| p.name == name) {
+      Some(project) => project.tags.retain(|t| t != tag),
+      None => return Err(format!("プロジェクト '{}' が存在しません", name).into()),
+    }
+    Ok(())
+  })?;
+  info!("プロジェクト '{}' からタグ '{}' を外しました", name, tag);
+  Ok(projects)
+}
+
+/// タグ名を全プロジェクトで変更（変更先のタグが既にあれば統合する）
+pub fn rename_tag(app: &AppHandle, config_dir: &PathBuf, from: &str, to: &str) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  let to = to.trim();
+  if to.is_empty() {
+    return Err("タグが空です".into());
+  }
+  let projects = update_projects(app, config_dir, |projects| {
+    for project in projects.iter_mut().filter(|p| p.tags.iter().any(|t| t == from)) {
+      project.tags.retain(|t| t != from && t != to);
+      project.tags.push(to.to_string());
+    }
+    Ok(())
+  })?;
+  info!("タグ '{}' を '{}' に変更しました", from, to);
+  Ok(projects)
+}
+
+/// タグを全プロジェクトから削除
+pub fn delete_tag(app: &AppHandle, config_dir: &PathBuf, tag: &str) -> Result<Vec<ProjectConfig>, Box<dyn std::error::Error>> {
+  let projects = update_projects(app, config_dir, |projects| {
+    for project in projects.iter_mut() {
+      project.tags.retain(|t| t != tag);
+    }
+    Ok(())
+  })?;
+  info!("タグ '{}' を削除しました", tag);
+  Ok(projects)
+}
+
+/// 登録済みプロジェクトを名前・パス・説明・備考・タグで検索
+/// 大文字小文字を区別しない部分一致。`query` が空の場合は絞り込み条件のみで検索する
+pub fn search_workspace(app: &AppHandle, config_dir: &PathBuf, query: &str, filters: &SearchFilters) -> Result<Vec<SearchResult>, Box<dyn std::error::Error>> {
+  let query = query.trim().to_lowercase();
+  let mut results = Vec::new();
+
+  for project in load_projects(app, config_dir)? {
+    // すべての絞り込みタグが付いているものだけを対象にする
+    if !filters.tags.iter().all(|tag| project.tags.contains(tag)) {
+      continue;
+    }
+
+    let mut matched_fields = Vec::new();
+    if !query.is_empty() {
+      let fields = [
+        ("name", project.name.as_str()),
+        ("filepath", project.filepath.as_str()),
+        ("description", project.description.as_str()),
+        ("remarks", project.remarks.as_str()),
+      ];
+      for (field, value) in fields {
+        if value.to_lowercase().contains(&query) {
+          matched_fields.push(field.to_string());
+        }
+      }
+      if project.tags.iter().any(|t| t.to_lowercase().contains(&query)) {
+        matched_fields.push(String::from("tags"));
+      }
+      if matched_fields.is_empty() {
+        continue;
+      }
+    }
+
+    results.push(SearchResult {
+      kind: String::from("project"),
+      name: project.name,
+      filepath: project.filepath,
+      matched_fields,
+    });
+  }
+  Ok(results)
+}
+
+/// プロジェクト別設定ファイルのパス
+pub fn project_settings_path(project_dir: &Path) -> PathBuf {
+  project_dir.join(".d4cs").join("settings.json")
+}
+
+/// プロジェクト別設定を読み込み
+/// ファイルが存在しない場合はデフォルト値で作成する
+pub fn load_project_settings(project_dir: &Path) -> Result<ProjectSettings, Box<dyn std::error::Error>> {
+  if !path_utils::for_io(project_dir).is_dir() {
+    return Err(format!("プロジェクトフォルダが存在しません: {}", project_dir.display()).into());
+  }
+  let path = project_settings_path(project_dir);
+  if !path_utils::for_io(&path).exists() {
+    let settings = ProjectSettings::default();
+    save_project_settings(project_dir, &settings)?;
+    info!("プロジェクト別設定をデフォルト作成しました: {}", path.display());
+    return Ok(settings);
+  }
+
+  let settings = serde_json::from_slice(&fs::read(path_utils::for_io(&path))?)?;
+  info!("プロジェクト別設定を読み込みました: {}", path.display());
+  Ok(settings)
+}
+
+/// プロジェクト別設定を保存
+pub fn save_project_settings(project_dir: &Path, settings: &ProjectSettings) -> Result<(), Box<dyn std::error::Error>> {
+  let path = project_settings_path(project_dir);
+  if let Some(dir) = path.parent() {
+    fs::create_dir_all(path_utils::for_io(dir))?;
+  }
+  write_access::check_writable(&path)?;
+  write_file_atomic(&path, &serde_json::to_vec_pretty(settings)?)?;
+  info!("プロジェクト別設定を保存しました: {}", path.display());
+  Ok(())
+}
+
+/// シークレット暗号化用の鍵を作成
+/// マシン固有 ID から導出するため、設定ファイルを別のマシンへコピーしても復号できない
+fn secret_key() -> Result<Aes256Gcm, Box<dyn std::error::Error>> {
+  let machine_id = machine_uid::get().map_err(|e| format!("マシン ID の取得に失敗しました: {}", e))?;
+  let mut hasher = Sha256::new();
+  hasher.update(b"D4CleaningStudio/secrets/v1");
+  hasher.update(machine_id.as_bytes());
+  let key = hasher.finalize();
+  Aes256Gcm::new_from_slice(&key).map_err(|e| format!("暗号鍵の作成に失敗しました: {}", e).into())
+}
+
+/// 保存済みシークレットを読み込み（暗号化されたまま）
+fn load_secrets(app: &AppHandle, config_dir: &PathBuf) -> Result<HashMap<String, EncryptedSecret>, Box<dyn std::error::Error>> {
+  Ok(read_key(&shared_store(app, config_dir)?, "secrets")?.unwrap_or_default())
+}
+
+/// シークレットを暗号化して保存
+/// ログには名前のみ出力し、値は出力しない
+pub fn set_secret(app: &AppHandle, config_dir: &PathBuf, name: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+  if name.trim().is_empty() {
+    return Err("シークレット名が空です".into());
+  }
+  let cipher = secret_key()?;
+  let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+  let ciphertext = cipher.encrypt(&nonce, value.as_bytes()).map_err(|_| "シークレットの暗号化に失敗しました")?;
+
+  with_store(app, config_dir, |tx| {
+    let mut secrets: HashMap<String, EncryptedSecret> = read_key(tx, "secrets")?.unwrap_or_default();
+    secrets.insert(
+      name.to_string(),
+      EncryptedSecret {
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+      },
+    );
+    tx.set("secrets", json!(secrets));
+    Ok(())
+  })?;
+  info!("シークレットを保存しました: {}", name);
+  Ok(())
+}
+
+/// シークレットを復号して取得
+/// 未登録の場合は None を返す
+pub fn get_secret(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+  let secret = match load_secrets(app, config_dir)?.remove(name) {
+    Some(s) => s,
+    None => return Ok(None),
+  };
+
+  let nonce_bytes = BASE64.decode(&secret.nonce)?;
+  if nonce_bytes.len() != 12 {
+    return Err(format!("シークレット '{}' のノンスが不正です", name).into());
+  }
+  let ciphertext = BASE64.decode(&secret.ciphertext)?;
+  let cipher = secret_key()?;
+  let plaintext = cipher
+    .decrypt(GenericArray::from_slice(&nonce_bytes), ciphertext.as_slice())
+    .map_err(|_| format!("シークレット '{}' を復号できません（別のマシンで保存された可能性があります）", name))?;
+  Ok(Some(String::from_utf8(plaintext)?))
+}
+
+/// シークレットを削除
+pub fn delete_secret(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+  with_store(app, config_dir, |tx| {
+    let mut secrets: HashMap<String, EncryptedSecret> = read_key(tx, "secrets")?.unwrap_or_default();
+    if secrets.remove(name).is_none() {
+      return Err(format!("シークレット '{}' が存在しません", name).into());
+    }
+    tx.set("secrets", json!(secrets));
+    Ok(())
+  })?;
+  info!("シークレットを削除しました: {}", name);
+  Ok(())
+}
+
+/// 登録済みシークレットの名前一覧（名前順、値は含まない）
+pub fn list_secret_names(app: &AppHandle, config_dir: &PathBuf) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+  let mut names: Vec<String> = load_secrets(app, config_dir)?.into_keys().collect();
+  names.sort();
+  Ok(names)
+}
+
+/// ワークスペースを読み込み
+/// 一覧から削除されたプロジェクトは除外して返す
+pub fn load_workspace(app: &AppHandle, config_dir: &PathBuf) -> Result<Workspace, Box<dyn std::error::Error>> {
+  workspace_in(&shared_store(app, config_dir)?)
+}
+
+/// ストアからワークスペースを読み込み、一覧から削除されたプロジェクトを除外する
+fn workspace_in(store: &Store<Wry>) -> Result<Workspace, Box<dyn std::error::Error>> {
+  let mut workspace: Workspace = read_key(store, "workspace")?.unwrap_or_default();
+  let projects: Vec<ProjectConfig> = read_key(store, "projects")?.unwrap_or_default();
+  workspace.open_projects.retain(|name| projects.iter().any(|p| &p.name == name));
+  if workspace.active.as_ref().is_some_and(|active| !workspace.open_projects.contains(active)) {
+    workspace.active = workspace.open_projects.last().cloned();
+  }
+  Ok(workspace)
+}
+
+/// ワークスペースを読み込み→変更→保存する
+/// アクティブなプロジェクトは現在のプロジェクト（project_config）にも反映する
+fn update_workspace(app: &AppHandle, config_dir: &PathBuf, f: impl FnOnce(&Store<Wry>, &mut Workspace) -> Result<(), Box<dyn std::error::Error>>) -> Result<Workspace, Box<dyn std::error::Error>> {
+  with_store(app, config_dir, |tx| {
+    let mut workspace = workspace_in(tx)?;
+    f(tx, &mut workspace)?;
+    tx.set("workspace", json!(workspace));
+    let projects: Vec<ProjectConfig> = read_key(tx, "projects")?.unwrap_or_default();
+    if let Some(project) = workspace.active.as_ref().and_then(|active| projects.iter().find(|p| &p.name == active)) {
+      tx.set("project_config", json!(project));
+    }
+    Ok(workspace)
+  })
+}
+
+/// プロジェクトをワークスペースで開き、アクティブにする
+/// 既に開いている場合はアクティブにするだけ
+pub fn open_project_in_workspace(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<Workspace, Box<dyn std::error::Error>> {
+  let workspace = update_workspace(app, config_dir, |tx, workspace| {
+    let projects: Vec<ProjectConfig> = read_key(tx, "projects")?.unwrap_or_default();
+    let project = match projects.into_iter().find(|p| p.name == name) {
+      Some(p) => p,
+      None => return Err(format!("プロジェクト '{}' が存在しません", name).into()),
+    };
+    push_recent_in(tx, &project.name, &project.filepath)?;
+
+    if !workspace.open_projects.iter().any(|n| n == name) {
+      workspace.open_projects.push(name.to_string());
+    }
+    workspace.active = Some(name.to_string());
+    Ok(())
+  })?;
+  info!("ワークスペースでプロジェクトを開きました: {}", name);
+  Ok(workspace)
+}
+
+/// ワークスペースからプロジェクトを閉じる
+/// アクティブなプロジェクトを閉じた場合は、最後に開いたプロジェクトをアクティブにする
+pub fn close_project(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<Workspace, Box<dyn std::error::Error>> {
+  let workspace = update_workspace(app, config_dir, |_, workspace| {
+    let before = workspace.open_projects.len();
+    workspace.open_projects.retain(|n| n != name);
+    if workspace.open_projects.len() == before {
+      return Err(format!("プロジェクト '{}' は開かれていません", name).into());
+    }
+    if workspace.active.as_deref() == Some(name) {
+      workspace.active = workspace.open_projects.last().cloned();
+    }
+    Ok(())
+  })?;
+  info!("ワークスペースのプロジェクトを閉じました: {}", name);
+  Ok(workspace)
+}
+
+/// ワークスペースで開いているプロジェクトをアクティブにする
+pub fn switch_active_project(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<Workspace, Box<dyn std::error::Error>> {
+  let workspace = update_workspace(app, config_dir, |_, workspace| {
+    if !workspace.open_projects.iter().any(|n| n == name) {
+      return Err(format!("プロジェクト '{}' は開かれていません", name).into());
+    }
+    workspace.active = Some(name.to_string());
+    Ok(())
+  })?;
+  info!("アクティブなプロジェクトを切り替えました: {}", name);
+  Ok(workspace)
+}
+
+/// ファイルダイアログの既定フォルダ設定を読み込み
+pub fn load_dialog_directories(app: &AppHandle, config_dir: &PathBuf) -> Result<DialogDirectories, Box<dyn std::error::Error>> {
+  Ok(read_key(&shared_store(app, config_dir)?, "dialog_directories")?.unwrap_or_default())
+}
+
+/// ファイルダイアログの既定フォルダ設定を保存
+pub fn save_dialog_directories(app: &AppHandle, config_dir: &PathBuf, dirs: &DialogDirectories) -> Result<(), Box<dyn std::error::Error>> {
+  validate_export_roots(dirs)?;
+  with_store(app, config_dir, |tx| {
+    tx.set("dialog_directories", json!(dirs));
+    Ok(())
+  })?;
+  info!("ファイルダイアログの既定フォルダ設定を保存しました");
+  Ok(())
+}
+
+/// ファイルダイアログで最初に開くフォルダを決定
+/// 優先順位: プロジェクト別設定 > 最後に使ったフォルダ（記憶する設定の場合） > 全体の既定フォルダ
+/// 存在しないフォルダは候補から外し、どれも無ければ None（OS の既定）を返す
+pub fn resolve_dialog_directory(app: &AppHandle, config_dir: &PathBuf, category: DialogCategory, project_dir: Option<&Path>) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+  let mut candidates: Vec<String> = Vec::new();
+  if let Some(project_dir) = project_dir.filter(|dir| path_utils::for_io(&project_settings_path(dir)).exists()) {
+    if let Some(dir) = load_project_settings(project_dir)?.default_directories.remove(&category) {
+      candidates.push(dir);
+    }
+  }
+  let mut dirs = load_dialog_directories(app, config_dir)?;
+  if dirs.remember_last_used {
+    candidates.extend(dirs.last_used.remove(&category));
+  }
+  candidates.extend(dirs.defaults.remove(&category));
+
+  Ok(candidates.into_iter().map(PathBuf::from).find(|dir| path_utils::for_io(dir).is_dir()))
+}
+
+/// 最後に使ったフォルダを記録（記憶しない設定の場合は何もしない）
+/// ファイルが渡された場合はその親フォルダを記録する
+pub fn remember_dialog_directory(app: &AppHandle, config_dir: &PathBuf, category: DialogCategory, selected: &Path) -> Result<(), Box<dyn std::error::Error>> {
+  let folder = if path_utils::for_io(selected).is_dir() { Some(selected) } else { selected.parent() };
+  let Some(folder) = folder else {
+    return Ok(());
+  };
+  let folder_text = folder.to_string_lossy().to_string();
+  if category == DialogCategory::Export {
+    // エクスポート先はファイルを開くことを許可するフォルダになるため、広すぎるフォルダは記録しない
+    if let Err(e) = safe_opener::validate_root(&folder_text) {
+      warn!("最後に使ったフォルダとして記録しませんでした: {}", e);
+      return Ok(());
+    }
+  }
+  with_store(app, config_dir, |tx| {
+    let mut dirs: DialogDirectories = read_key(tx, "dialog_directories")?.unwrap_or_default();
+    if dirs.remember_last_used {
+      dirs.last_used.insert(category, folder_text);
+      tx.set("dialog_directories", json!(dirs));
+    }
+    Ok(())
+  })
+}
+
+/// キーボードショートカットを読み込み
+/// 後から追加された操作は初期値で補う
+pub fn load_keybindings(app: &AppHandle, config_dir: &PathBuf) -> Result<Vec<Keybinding>, Box<dyn std::error::Error>> {
+  keybindings_in(&shared_store(app, config_dir)?)
+}
+
+/// ストアからキーボードショートカットを読み込み、後から追加された操作を初期値で補う
+fn keybindings_in(store: &Store<Wry>) -> Result<Vec<Keybinding>, Box<dyn std::error::Error>> {
+  let mut keybindings: Vec<Keybinding> = read_key(store, "keybindings")?.unwrap_or_default();
+  for default in default_keybindings() {
+    if !keybindings.iter().any(|k| k.action == default.action) {
+      keybindings.push(default);
+    }
+  }
+  Ok(keybindings)
+}
+
+/// キーの組み合わせを正規化（修飾キーの順序を Ctrl, Alt, Shift, Meta に揃え、1文字のキーは大文字にする）
+pub fn normalize_shortcut(shortcut: &str) -> Result<String, Box<dyn std::error::Error>> {
+  let shortcut = shortcut.trim();
+  if shortcut.is_empty() {
+    return Ok(String::new());
+  }
+  // "Alt+-" や "Ctrl++" のように区切りの "+" 自体がキーになる場合に対応
+  let (modifiers, key) = match shortcut.strip_suffix("++") {
+    Some(rest) => (rest, "+"),
+    None => match shortcut.rsplit_once('+') {
+      Some((rest, key)) => (rest, key),
+      None => ("", shortcut),
+    },
+  };
+  if key.is_empty() {
+    return Err(format!("キーが指定されていません: {}", shortcut).into());
+  }
+
+  let mut flags = [false; 4];
+  for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+    let index = match modifier.to_lowercase().as_str() {
+      "ctrl" | "control" | "cmdorctrl" => 0,
+      "alt" | "option" => 1,
+      "shift" => 2,
+      "meta" | "cmd" | "command" | "super" => 3,
+      other => return Err(format!("不明な修飾キーです: {}", other).into()),
+    };
+    flags[index] = true;
+  }
+
+  let mut parts: Vec<String> = ["Ctrl", "Alt", "Shift", "Meta"].iter().zip(flags).filter(|(_, on)| *on).map(|(name, _)| name.to_string()).collect();
+  parts.push(if key.chars().count() == 1 { key.to_uppercase() } else { key.to_string() });
+  Ok(parts.join("+"))
+}
+
+/// `action` に `shortcut`（正規化済み）を割り当てると衝突する他の割り当てを探す
+/// 同じ範囲どうし、またはどちらかがアプリ全体の場合に衝突とする（空文字は割り当てなしのため衝突しない）
+fn find_keybinding_conflict<'a>(keybindings: &'a [Keybinding], action: &str, context: KeybindingContext, shortcut: &str) -> Option<&'a Keybinding> {
+  if shortcut.is_empty() {
+    return None;
+  }
+  keybindings.iter().find(|k| k.action != action && k.shortcut == shortcut && k.context.overlaps(context))
+}
+
+/// キーボードショートカットを変更
+/// 同じ範囲（またはアプリ全体）で同じキーが既に使われている場合はエラーにする
+///
+/// # 戻り値
+/// * 変更後のショートカット一覧
+pub fn set_keybinding(app: &AppHandle, config_dir: &PathBuf, action: &str, shortcut: &str) -> Result<Vec<Keybinding>, Box<dyn std::error::Error>> {
+  let shortcut = normalize_shortcut(shortcut)?;
+  let keybindings = with_store(app, config_dir, |tx| {
+    let mut keybindings = keybindings_in(tx)?;
+    let context = match keybindings.iter().find(|k| k.action == action) {
+      Some(binding) => binding.context,
+      None => return Err(format!("操作 '{}' が見つかりません", action).into()),
+    };
+
+    if let Some(conflict) = find_keybinding_conflict(&keybindings, action, context, &shortcut) {
+      return Err(format!("{} は既に '{}' に割り当てられています", shortcut, conflict.action).into());
+    }
+
+    if let Some(binding) = keybindings.iter_mut().find(|k| k.action == action) {
+      binding.shortcut = shortcut.clone();
+    }
+    tx.set("keybindings", json!(keybindings));
+    Ok(keybindings)
+  })?;
+  info!("ショートカットを変更しました: {} -> {}", action, shortcut);
+  Ok(keybindings)
+}
+
+/// キーボードショートカットを初期値に戻す
+/// `action` を指定した場合はその操作だけを戻す（初期値が他の割り当てと衝突する場合はエラー）
+///
+/// # 戻り値
+/// * 変更後のショートカット一覧
+pub fn reset_keybindings(app: &AppHandle, config_dir: &PathBuf, action: Option<&str>) -> Result<Vec<Keybinding>, Box<dyn std::error::Error>> {
+  let keybindings = match action {
+    Some(action) => {
+      let default = default_keybindings()
+        .into_iter()
+        .find(|k| k.action == action)
+        .ok_or_else(|| format!("操作 '{}' が見つかりません", action))?;
+      return set_keybinding(app, config_dir, action, &default.shortcut);
+    },
+    None => default_keybindings(),
+  };
+  with_store(app, config_dir, |tx| {
+    tx.set("keybindings", json!(keybindings));
+    Ok(())
+  })?;
+  info!("ショートカットをすべて初期値に戻しました");
+  Ok(keybindings)
+}
+
+/// 色の値が CSS の色として使える形式か検証
+/// `#RGB` / `#RRGGBB` / `#RRGGBBAA` と、`rgb()` `rgba()` `hsl()` `hsla()` `oklch()` の関数表記を受け付ける
+pub fn is_valid_color(value: &str) -> bool {
+  let value = value.trim();
+  if let Some(hex) = value.strip_prefix('#') {
+    return matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit());
+  }
+  let Some((function, rest)) = value.split_once('(') else {
+    return false;
+  };
+  let Some(args) = rest.strip_suffix(')') else {
+    return false;
+  };
+  if !matches!(function.trim().to_lowercase().as_str(), "rgb" | "rgba" | "hsl" | "hsla" | "oklch") {
+    return false;
+  }
+  // 各成分は数値（`%` や角度の `deg` 付きも可）
+  let components: Vec<&str> = args.split([',', '/', ' ']).filter(|c| !c.is_empty()).collect();
+  (3..=4).contains(&components.len())
+    && components.iter().all(|c| {
+      let number = c.strip_suffix('%').or_else(|| c.strip_suffix("deg")).unwrap_or(*c);
+      number.parse::<f64>().is_ok()
+    })
+}
+
+/// カスタムテーマの名前と色を検証
+fn validate_custom_theme(theme: &CustomTheme) -> Result<(), Box<dyn std::error::Error>> {
+  let name = theme.name.trim();
+  if name.is_empty() {
+    return Err("テーマ名が空です".into());
+  }
+  if BUILTIN_THEMES.iter().any(|builtin| builtin.eq_ignore_ascii_case(name)) {
+    return Err(format!("'{}' は組み込みテーマの名前のため使えません", name).into());
+  }
+  let invalid: Vec<String> = theme
+    .colors
+    .entries()
+    .iter()
+    .filter(|(_, value)| !is_valid_color(value))
+    .map(|(key, value)| format!("{} = '{}'", key, value))
+    .collect();
+  if !invalid.is_empty() {
+    return Err(format!("色の値が正しくありません: {}", invalid.join(", ")).into());
+  }
+  Ok(())
+}
+
+/// 既存のカスタムテーマと名前が重複するかどうか（`current` は更新対象の変更前の名前）
+/// 組み込みテーマとの比較（`validate_custom_theme`）と異なり、大文字小文字を区別する
+fn is_theme_name_taken(themes: &[CustomTheme], name: &str, current: Option<&str>) -> bool {
+  current != Some(name) && themes.iter().any(|t| t.name == name)
+}
+
+/// カスタムテーマの一覧を読み込み
+pub fn load_custom_themes(app: &AppHandle, config_dir: &PathBuf) -> Result<Vec<CustomTheme>, Box<dyn std::error::Error>> {
+  Ok(read_key(&shared_store(app, config_dir)?, "custom_themes")?.unwrap_or_default())
+}
+
+/// カスタムテーマを作成
+///
+/// # 戻り値
+/// * 作成後のカスタムテーマ一覧
+pub fn create_custom_theme(app: &AppHandle, config_dir: &PathBuf, mut theme: CustomTheme) -> Result<Vec<CustomTheme>, Box<dyn std::error::Error>> {
+  theme.name = theme.name.trim().to_string();
+  validate_custom_theme(&theme)?;
+  let name = theme.name.clone();
+  let themes = with_store(app, config_dir, |tx| {
+    let mut themes: Vec<CustomTheme> = read_key(tx, "custom_themes")?.unwrap_or_default();
+    if is_theme_name_taken(&themes, &theme.name, None) {
+      return Err(format!("テーマ '{}' は既に存在します", theme.name).into());
+    }
+    themes.push(theme);
+    tx.set("custom_themes", json!(themes));
+    Ok(themes)
+  })?;
+  info!("カスタムテーマを作成しました: {}", name);
+  Ok(themes)
+}
+
+/// カスタムテーマを更新
+/// 名前を変更した場合、使用中のテーマであれば `WindowState.theme` も新しい名前に更新する
+///
+/// # 戻り値
+/// * 更新後のカスタムテーマ一覧
+pub fn update_custom_theme(app: &AppHandle, config_dir: &PathBuf, name: &str, mut theme: CustomTheme) -> Result<Vec<CustomTheme>, Box<dyn std::error::Error>> {
+  theme.name = theme.name.trim().to_string();
+  validate_custom_theme(&theme)?;
+  let themes = with_store(app, config_dir, |tx| {
+    let mut themes: Vec<CustomTheme> = read_key(tx, "custom_themes")?.unwrap_or_default();
+    if is_theme_name_taken(&themes, &theme.name, Some(name)) {
+      return Err(format!("テーマ '{}' は既に存在します", theme.name).into());
+    }
+    let index = themes.iter().position(|t| t.name == name).ok_or_else(|| format!("テーマ '{}' が見つかりません", name))?;
+    themes[index] = theme.clone();
+    tx.set("custom_themes", json!(themes));
+
+    let mut state: WindowState = read_key(tx, "window_state")?.ok_or("window_state が存在しません")?;
+    if state.theme == name {
+      state.theme = theme.name.clone();
+      tx.set("window_state", json!(state));
+    }
+    Ok(themes)
+  })?;
+  info!("カスタムテーマを更新しました: {} -> {}", name, theme.name);
+  Ok(themes)
+}
+
+/// カスタムテーマを削除
+/// 使用中のテーマを削除した場合は `WindowState.theme` を既定値（auto）に戻す
+///
+/// # 戻り値
+/// * 削除後のカスタムテーマ一覧
+pub fn delete_custom_theme(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<Vec<CustomTheme>, Box<dyn std::error::Error>> {
+  let themes = with_store(app, config_dir, |tx| {
+    let mut themes: Vec<CustomTheme> = read_key(tx, "custom_themes")?.unwrap_or_default();
+    let before = themes.len();
+    themes.retain(|t| t.name != name);
+    if themes.len() == before {
+      return Err(format!("テーマ '{}' が見つかりません", name).into());
+    }
+    tx.set("custom_themes", json!(themes));
+
+    let mut state: WindowState = read_key(tx, "window_state")?.ok_or("window_state が存在しません")?;
+    if state.theme == name {
+      state.theme = Config::default().window_state.theme;
+      tx.set("window_state", json!(state));
+      warn!("使用中のテーマ '{}' を削除したため、テーマを {} に戻しました", name, state.theme);
+    }
+    Ok(themes)
+  })?;
+  info!("カスタムテーマを削除しました: {}", name);
+  Ok(themes)
+}
+
+/// ウィンドウ基本設定（タイトル・最小/最大サイズ）をウィンドウへ適用
+pub fn apply_window_config(window: &WebviewWindow, config: &WindowConfig) {
+  if let Err(e) = window.set_title(&config.title) {
+    error!("タイトルの設定に失敗しました: {}", e);
+  }
+  if let Err(e) = window.set_min_size(Some(tauri::Size::Physical(PhysicalSize {
+    width: config.min_width,
+    height: config.min_height,
+  }))) {
+    error!("最小サイズの設定に失敗しました: {}", e);
+  }
+  if let Err(e) = window.set_max_size(Some(tauri::Size::Physical(PhysicalSize {
+    width: config.max_width,
+    height: config.max_height,
+  }))) {
+    error!("最大サイズの設定に失敗しました: {}", e);
+  }
+}
+
+/// プロファイルを切り替えても変更しないキー
+/// シークレットと同期設定はプロファイルではなくこのマシンに属する
+const PROFILE_LOCAL_KEYS: &[&str] = &["secrets", "settings_sync"];
+
+/// 設定プロファイルの保存先フォルダ（`<設定ディレクトリ>/profiles`）
+fn profiles_dir(config_dir: &Path) -> PathBuf {
+  config_dir.join("profiles")
+}
+
+/// 設定プロファイルのファイルパス
+/// 名前は英数字（日本語を含む）・`-`・`_` のみ受け付け、Windows の予約名（CON, NUL など）は使えない
+fn profile_path(config_dir: &Path, name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+  if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+    return Err(format!("プロファイル名 '{}' は使えません（英数字・-・_ のみ）", name).into());
+  }
+  if path_utils::is_reserved_name(name) {
+    return Err(format!("プロファイル名 '{}' は Windows の予約名のため使えません", name).into());
+  }
+  Ok(profiles_dir(config_dir).join(format!("{}.json", name)))
+}
+
+/// 使用中の設定プロファイル名を読み込み
+pub fn load_active_profile(app: &AppHandle, config_dir: &PathBuf) -> Result<String, Box<dyn std::error::Error>> {
+  let store = shared_store(app, config_dir)?;
+  Ok(store.get("active_profile").and_then(|v| v.as_str().map(String::from)).unwrap_or_else(|| DEFAULT_PROFILE.to_string()))
+}
+
+/// 設定プロファイルの一覧を取得
+/// 使用中のプロファイルはまだファイルに書き出されていなくても一覧に含める
+pub fn list_profiles(app: &AppHandle, config_dir: &PathBuf) -> Result<ProfileList, Box<dyn std::error::Error>> {
+  let active = load_active_profile(app, config_dir)?;
+  let mut profiles = vec![active.clone()];
+  if let Ok(entries) = fs::read_dir(path_utils::for_io(&profiles_dir(config_dir))) {
+    for entry in entries.flatten() {
+      let path = entry.path();
+      if path.extension().is_some_and(|ext| ext == "json") {
+        if let Some(name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) {
+          if !profiles.contains(&name) {
+            profiles.push(name);
+          }
+        }
+      }
+    }
+  }
+  profiles.sort();
+  Ok(ProfileList { active, profiles })
+}
+
+/// デフォルト設定で新しい設定プロファイルを作成
+pub fn create_profile(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<ProfileList, Box<dyn std::error::Error>> {
+  let path = profile_path(config_dir, name)?;
+  if list_profiles(app, config_dir)?.profiles.iter().any(|p| p == name) {
+    return Err(format!("プロファイル '{}' は既に存在します", name).into());
+  }
+
+  let mut entries = match serde_json::to_value(Config::default())? {
+    Value::Object(map) => map,
+    _ => return Err("デフォルト設定の生成に失敗しました".into()),
+  };
+  entries.insert(String::from("project_config"), json!(ProjectConfig::default()));
+  entries.insert(String::from("schema_version"), json!(CURRENT_SCHEMA_VERSION));
+  entries.insert(String::from("active_profile"), json!(name));
+
+  fs::create_dir_all(path_utils::for_io(&profiles_dir(config_dir)))?;
+  write_file_atomic(&path, &serde_json::to_vec_pretty(&entries)?)?;
+  info!("設定プロファイルを作成しました: {}", name);
+  list_profiles(app, config_dir)
+}
+
+/// 既存の設定プロファイルを複製
+/// 使用中のプロファイルを複製する場合は、現在の設定をそのまま書き出す
+pub fn duplicate_profile(app: &AppHandle, config_dir: &PathBuf, source: &str, name: &str) -> Result<ProfileList, Box<dyn std::error::Error>> {
+  let source_path = profile_path(config_dir, source)?;
+  let path = profile_path(config_dir, name)?;
+  let list = list_profiles(app, config_dir)?;
+  if list.profiles.iter().any(|p| p == name) {
+    return Err(format!("プロファイル '{}' は既に存在します", name).into());
+  }
+
+  fs::create_dir_all(path_utils::for_io(&profiles_dir(config_dir)))?;
+  if list.active == source {
+    export_settings(app, config_dir, &path)?;
+  } else if path_utils::for_io(&source_path).exists() {
+    fs::copy(path_utils::for_io(&source_path), path_utils::for_io(&path))?;
+  } else {
+    return Err(format!("プロファイル '{}' が見つかりません", source).into());
+  }
+  info!("設定プロファイルを複製しました: {} -> {}", source, name);
+  list_profiles(app, config_dir)
+}
+
+/// 使用中の設定プロファイルを切り替える
+/// 1. 現在の設定を使用中のプロファイルのファイルへ書き出す
+/// 2. 切り替え先のファイルを検証して読み込む（読み込めない場合は切り替えない）
+pub fn switch_profile(app: &AppHandle, config_dir: &PathBuf, name: &str) -> Result<ProfileList, Box<dyn std::error::Error>> {
+  let path = profile_path(config_dir, name)?;
+  let active = load_active_profile(app, config_dir)?;
+  if active == name {
+    return list_profiles(app, config_dir);
+  }
+  if !path_utils::for_io(&path).exists() {
+    return Err(format!("プロファイル '{}' が見つかりません", name).into());
+  }
+
+  fs::create_dir_all(path_utils::for_io(&profiles_dir(config_dir)))?;
+  export_settings(app, config_dir, &profile_path(config_dir, &active)?)?;
+  import_settings_keeping(app, config_dir, &path, PROFILE_LOCAL_KEYS)?;
+  with_store(app, config_dir, |tx| {
+    tx.set("active_profile", json!(name));
+    Ok(())
+  })?;
+  info!("設定プロファイルを切り替えました: {} -> {}", active, name);
+  list_profiles(app, config_dir)
+}
+
+/// 同期先フォルダに置く設定ファイル名
+pub const SYNC_FILE_NAME: &str = "D4CleaningStudio.sync.json";
+
+/// 前回の同期状態を記録するファイル名（設定ディレクトリ内、同期対象外）
+const SYNC_STATE_FILE_NAME: &str = "sync-state.json";
+
+/// 同期しないキー（マシン固有の値）
+/// シークレットはマシン固有の鍵で暗号化しているため、別のマシンでは復号できない
+const SYNC_LOCAL_KEYS: &[&str] = &["secrets", "settings_sync", "active_profile"];
+
+/// 前回の同期完了時の両方の設定内容のハッシュ（同期対象のキーのみ）
+/// 設定ファイルは起動・終了のたびに保存し直されるため、更新日時ではなく内容で変更を判定する
+/// 設定ファイル自体に書くと内容が変わってしまうため、別ファイルに記録する
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+struct SyncState {
+  local_hash: String,
+  remote_hash: String,
+}
+
+/// 同期の方向（前回の同期状態と現在の内容から決める）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SyncDirection {
+  UpToDate, // どちらも変更なし
+  Push,     // このマシンの設定を同期先へ書き出す
+  Pull,     // 同期先の設定を読み込む
+  Conflict, // 両方が変更されている
+}
+
+/// 同期対象のキー（`SYNC_LOCAL_KEYS` 以外）の内容ハッシュ
+/// キーの順序に左右されないよう、オブジェクトのキーを並べ替えてから FNV-1a (64bit) にかける
+fn sync_content_hash(entries: &serde_json::Map<String, Value>) -> String {
+  fn canonical(value: &Value) -> Value {
+    match value {
+      Value::Object(map) => {
+        let mut keys: Vec<&String> = map.keys().collect();
+        keys.sort();
+        Value::Object(keys.into_iter().map(|key| (key.clone(), canonical(&map[key]))).collect())
+      },
+      Value::Array(items) => Value::Array(items.iter().map(canonical).collect()),
+      other => other.clone(),
+    }
+  }
+
+  let synced: serde_json::Map<String, Value> = entries
+    .iter()
+    .filter(|(key, _)| !SYNC_LOCAL_KEYS.contains(&key.as_str()))
+    .map(|(key, value)| (key.clone(), value.clone()))
+    .collect();
+  let bytes = canonical(&Value::Object(synced)).to_string();
+  format!("{:016x}", fnv1a(0xcbf2_9ce4_8422_2325, bytes.as_bytes()))
+}
+
+/// 前回の同期状態と両方の内容ハッシュから同期の方向を決める
+/// 同期先に設定がなければ書き出し、初回同期で内容が異なれば競合とする
+fn sync_direction(state: Option<&SyncState>, local_hash: &str, remote_hash: Option<&str>, prefer: Option<SyncPreference>) -> SyncDirection {
+  let Some(remote_hash) = remote_hash else {
+    return SyncDirection::Push;
+  };
+  let (local_changed, remote_changed) = match state {
+    Some(state) => (local_hash != state.local_hash, remote_hash != state.remote_hash),
+    None if local_hash == remote_hash => (false, false),
+    None => (true, true), // 初回同期で両方に異なる設定がある
+  };
+  match (local_changed, remote_changed, prefer) {
+    (false, false, _) => SyncDirection::UpToDate,
+    (true, false, _) | (true, true, Some(SyncPreference::Local)) => SyncDirection::Push,
+    (false, true, _) | (true, true, Some(SyncPreference::Remote)) => SyncDirection::Pull,
+    (true, true, None) => SyncDirection::Conflict,
+  }
+}
+
+/// ファイルの更新日時（UNIX エポックからのミリ秒、ファイルがなければ None）
+fn modified_millis(path: &Path) -> Option<u64> {
+  let modified = fs::metadata(path_utils::for_io(path)).ok()?.modified().ok()?;
+  modified.duration_since(std::time::UNIX_EPOCH).ok().map(|d| d.as_millis() as u64)
+}
+
+/// UNIX エポックからのミリ秒を RFC 3339 形式の日時にする
+fn millis_to_rfc3339(millis: u64) -> String {
+  let time = std::time::UNIX_EPOCH + std::time::Duration::from_millis(millis);
+  chrono::DateTime::<Local>::from(time).to_rfc3339()
+}
+
+/// 設定の同期設定を読み込み
+pub fn load_settings_sync(app: &AppHandle, config_dir: &PathBuf) -> Result<SettingsSync, Box<dyn std::error::Error>> {
+  let store = shared_store(app, config_dir)?;
+  match store.get("settings_sync") {
+    Some(value) => Ok(serde_json::from_value(value)?),
+    None => Ok(SettingsSync::default()),
+  }
+}
+
+/// 設定の同期設定を保存
+/// 同期先を変更した場合は前回の同期状態を破棄する（次回は新しい同期先との初回同期になる）
+pub fn save_settings_sync(app: &AppHandle, config_dir: &PathBuf, sync: &SettingsSync) -> Result<(), Box<dyn std::error::Error>> {
+  if sync.enabled {
+    match sync.folder.as_deref().map(Path::new) {
+      Some(folder) if path_utils::for_io(folder).is_dir() => {},
+      Some(folder) => return Err(format!("同期先フォルダが存在しません: {}", folder.display()).into()),
+      None => return Err("同期先フォルダが指定されていません".into()),
+    }
+  }
+  with_store(app, config_dir, |tx| {
+    let current: SettingsSync = read_key(tx, "settings_sync")?.unwrap_or_default();
+    if current.folder != sync.folder {
+      let _ = fs::remove_file(path_utils::for_io(&config_dir.join(SYNC_STATE_FILE_NAME)));
+    }
+    tx.set("settings_sync", json!(sync));
+    Ok(())
+  })?;
+  info!("設定の同期設定を保存しました: {:?}", sync);
+  Ok(())
+}
+
+/// 設定を同期先フォルダと同期する
+/// 前回の同期以降に内容が変わったかを比較し、変更された側の設定でもう一方を更新する
+/// 両方が変更されている場合は `prefer` の指定がなければ何もせず競合として返す
+pub fn sync_settings(app: &AppHandle, config_dir: &PathBuf, prefer: Option<SyncPreference>) -> Result<SyncOutcome, Box<dyn std::error::Error>> {
+  let sync = load_settings_sync(app, config_dir)?;
+  let folder = match (sync.enabled, sync.folder) {
+    (true, Some(folder)) => PathBuf::from(folder),
+    _ => return Err("設定の同期が有効になっていません".into()),
+  };
+  let local_path = config_dir.join(CONFIG_FILE_NAME);
+  let remote_path = folder.join(SYNC_FILE_NAME);
+  let state_path = config_dir.join(SYNC_STATE_FILE_NAME);
+  let state: Option<SyncState> = fs::read(path_utils::for_io(&state_path)).ok().and_then(|bytes| serde_json::from_slice(&bytes).ok());
+
+  let local_entries = || -> Result<serde_json::Map<String, Value>, Box<dyn std::error::Error>> { Ok(shared_store(app, config_dir)?.entries().into_iter().collect()) };
+  let remote_entries = || -> Result<Option<serde_json::Map<String, Value>>, Box<dyn std::error::Error>> {
+    if path_utils::for_io(&remote_path).exists() {
+      Ok(Some(read_settings_file(&remote_path)?))
+    } else {
+      Ok(None)
+    }
+  };
+
+  let local_hash = sync_content_hash(&local_entries()?);
+  let remote_hash = remote_entries()?.map(|entries| sync_content_hash(&entries));
+  let outcome = match sync_direction(state.as_ref(), &local_hash, remote_hash.as_deref(), prefer) {
+    SyncDirection::UpToDate => return Ok(SyncOutcome::UpToDate),
+    SyncDirection::Conflict => {
+      warn!("設定の同期で競合が発生しました（両方が前回の同期以降に変更されています）");
+      return Ok(SyncOutcome::Conflict {
+        local_modified: millis_to_rfc3339(modified_millis(&local_path).unwrap_or(0)),
+        remote_modified: millis_to_rfc3339(modified_millis(&remote_path).unwrap_or(0)),
+      });
+    },
+    SyncDirection::Push => {
+      let mut entries = local_entries()?;
+      entries.retain(|key, _| !SYNC_LOCAL_KEYS.contains(&key.as_str()));
+      write_access::check_writable(&remote_path)?;
+      write_file_atomic(&remote_path, &serde_json::to_vec_pretty(&entries)?)?;
+      info!("設定を同期先へ書き出しました: {}", remote_path.display());
+      SyncOutcome::Pushed
+    },
+    SyncDirection::Pull => {
+      let mut entries = remote_entries()?.ok_or("同期先の設定ファイルが見つかりません")?;
+      entries.retain(|key, _| !SYNC_LOCAL_KEYS.contains(&key.as_str()));
+      replace_settings(app, config_dir, entries, SYNC_LOCAL_KEYS)?;
+      info!("同期先の設定を読み込みました: {}", remote_path.display());
+      SyncOutcome::Pulled
+    },
+  };
+
+  // 同期後の内容ハッシュを記録（読み込み時の補完・修復後の内容を基準にする）
+  let state = SyncState {
+    local_hash: sync_content_hash(&local_entries()?),
+    remote_hash: remote_entries()?.map(|entries| sync_content_hash(&entries)).unwrap_or_default(),
+  };
+  write_file_atomic(&state_path, &serde_json::to_vec_pretty(&state)?)?;
+  Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn migrated(mut entries: serde_json::Map<String, Value>) -> serde_json::Map<String, Value> {
+    assert!(migrate_entries(&mut entries).unwrap());
+    entries
+  }
+
+  fn sync_state(local_hash: &str, remote_hash: &str) -> SyncState {
+    SyncState {
+      local_hash: local_hash.to_string(),
+      remote_hash: remote_hash.to_string(),
+    }
+  }
+
+  #[test]
+  fn sync_direction_pushes_when_only_local_changed() {
+    let state = sync_state("a", "b");
+    assert_eq!(sync_direction(Some(&state), "a2", Some("b"), None), SyncDirection::Push);
+    assert_eq!(sync_direction(Some(&state), "a", None, None), SyncDirection::Push);
+    assert_eq!(sync_direction(None, "a", None, None), SyncDirection::Push);
+  }
+
+  #[test]
+  fn sync_direction_pulls_when_only_remote_changed() {
+    assert_eq!(sync_direction(Some(&sync_state("a", "b")), "a", Some("b2"), None), SyncDirection::Pull);
+  }
+
+  #[test]
+  fn sync_direction_reports_conflict_unless_preferred() {
+    let state = sync_state("a", "b");
+    assert_eq!(sync_direction(Some(&state), "a2", Some("b2"), None), SyncDirection::Conflict);
+    assert_eq!(sync_direction(Some(&state), "a2", Some("b2"), Some(SyncPreference::Local)), SyncDirection::Push);
+    assert_eq!(sync_direction(Some(&state), "a2", Some("b2"), Some(SyncPreference::Remote)), SyncDirection::Pull);
+    assert_eq!(sync_direction(None, "a", Some("b"), None), SyncDirection::Conflict);
+  }
+
+  #[test]
+  fn sync_direction_is_up_to_date_without_changes() {
+    assert_eq!(sync_direction(Some(&sync_state("a", "b")), "a", Some("b"), None), SyncDirection::UpToDate);
+    assert_eq!(sync_direction(None, "a", Some("a"), None), SyncDirection::UpToDate);
+  }
+
+  #[test]
+  fn sync_content_hash_ignores_local_keys_and_key_order() {
+    let mut first = serde_json::Map::new();
+    first.insert(String::from("window_state"), json!({ "width": 800, "height": 600 }));
+    first.insert(String::from("projects"), json!([]));
+    let mut second = serde_json::Map::new();
+    second.insert(String::from("projects"), json!([]));
+    second.insert(String::from("window_state"), json!({ "height": 600, "width": 800 }));
+    second.insert(String::from("secrets"), json!({ "token": "x" }));
+    assert_eq!(sync_content_hash(&first), sync_content_hash(&second));
+
+    second.insert(String::from("window_state"), json!({ "height": 600, "width": 1024 }));
+    assert_ne!(sync_content_hash(&first), sync_content_hash(&second));
+  }
+
+  fn os_args(args: &[&str]) -> impl Iterator<Item = OsString> {
+    args.iter().map(OsString::from).collect::<Vec<_>>().into_iter()
+  }
+
+  #[test]
+  fn config_dir_from_args_reads_separate_value() {
+    assert_eq!(config_dir_from_args(os_args(&["--verbose", "--config-dir", "/tmp/d4cs"])), Some(PathBuf::from("/tmp/d4cs")));
+  }
+
+  #[test]
+  fn config_dir_from_args_reads_inline_value() {
+    assert_eq!(config_dir_from_args(os_args(&["--config-dir=/tmp/d4cs"])), Some(PathBuf::from("/tmp/d4cs")));
+    assert_eq!(config_dir_from_args(os_args(&["--config-dir="])), None);
+  }
+
+  #[test]
+  fn config_dir_from_args_ignores_missing_value() {
+    assert_eq!(config_dir_from_args(os_args(&["--config-dir"])), None);
+    assert_eq!(config_dir_from_args(os_args(&["--config-directory=/tmp/d4cs"])), None);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn config_dir_from_args_accepts_non_utf8_arguments() {
+    use std::os::unix::ffi::OsStringExt;
+    let invalid = OsString::from_vec(vec![0x66, 0x6f, 0x80]);
+    let args = vec![invalid.clone(), OsString::from("--config-dir"), invalid.clone()];
+    assert_eq!(config_dir_from_args(args.into_iter()), Some(PathBuf::from(invalid)));
+  }
+
+  #[test]
+  fn config_dir_from_falls_back_to_env() {
+    let env = Some(OsString::from("/env/d4cs"));
+    assert_eq!(config_dir_from(os_args(&["--config-dir", "/arg/d4cs"]), env.clone()), Some(PathBuf::from("/arg/d4cs")));
+    assert_eq!(config_dir_from(os_args(&[]), env), Some(PathBuf::from("/env/d4cs")));
+    assert_eq!(config_dir_from(os_args(&[]), Some(OsString::new())), None);
+    assert_eq!(config_dir_from(os_args(&[]), None), None);
+  }
+
+  #[test]
+  fn is_valid_color_accepts_css_colors() {
+    for value in [
+      "#fff",
+      "#FFFA",
+      "#1e90ff",
+      "#1E90FF80",
+      " #abc ",
+      "rgb(0, 128, 255)",
+      "rgba(0 128 255 / 50%)",
+      "RGB(10%, 20%, 30%)",
+      "hsl(120deg 50% 50%)",
+      "oklch(70% 0.1 200)",
+    ] {
+      assert!(is_valid_color(value), "{}", value);
+    }
+  }
+
+  #[test]
+  fn is_valid_color_rejects_malformed_values() {
+    for value in [
+      "",
+      "fff",
+      "#ff",
+      "#12345",
+      "#1234567",
+      "#ggg",
+      "red",
+      "rgb(1, 2)",
+      "rgb(1, 2, 3",
+      "rgb(a, b, c)",
+      "rgb(1, 2, 3, 4, 5)",
+      "cmyk(0, 0, 0, 0)",
+    ] {
+      assert!(!is_valid_color(value), "{}", value);
+    }
+  }
+
+  fn custom_theme(name: &str) -> CustomTheme {
+    let color = String::from("#000000");
+    CustomTheme {
+      name: name.to_string(),
+      dark: false,
+      colors: ThemeColors {
+        primary: color.clone(),
+        secondary: color.clone(),
+        accent: color.clone(),
+        neutral: color.clone(),
+        base_100: color.clone(),
+        base_200: color.clone(),
+        base_300: color.clone(),
+        base_content: color.clone(),
+        info: color.clone(),
+        success: color.clone(),
+        warning: color.clone(),
+        error: color,
+      },
+    }
+  }
+
+  #[test]
+  fn validate_custom_theme_rejects_builtin_names_ignoring_case() {
+    for name in ["Light", "dark", "AUTO", ""] {
+      assert!(validate_custom_theme(&custom_theme(name)).is_err(), "{}", name);
+    }
+    assert!(validate_custom_theme(&custom_theme("Ocean")).is_ok());
+
+    let mut theme = custom_theme("Ocean");
+    theme.colors.accent = String::from("blue");
+    assert!(validate_custom_theme(&theme).is_err());
+  }
+
+  #[test]
+  fn is_theme_name_taken_is_case_sensitive() {
+    let themes = [custom_theme("Ocean")];
+    assert!(is_theme_name_taken(&themes, "Ocean", None));
+    assert!(!is_theme_name_taken(&themes, "ocean", None));
+    // 更新時に名前を変えなければ重複としない
+    assert!(!is_theme_name_taken(&themes, "Ocean", Some("Ocean")));
+    assert!(is_theme_name_taken(&[custom_theme("Ocean"), custom_theme("Forest")], "Ocean", Some("Forest")));
+  }
+
+  #[test]
+  fn normalize_shortcut_orders_modifiers_and_folds_case() {
+    for (input, expected) in [
+      ("shift+ctrl+s", "Ctrl+Shift+S"),
+      ("Meta+Option+a", "Alt+Meta+A"),
+      ("CmdOrCtrl+z", "Ctrl+Z"),
+      ("control+Shift+n", "Ctrl+Shift+N"),
+      (" F5 ", "F5"),
+      ("ctrl+Delete", "Ctrl+Delete"),
+      ("", ""),
+    ] {
+      assert_eq!(normalize_shortcut(input).unwrap(), expected, "{}", input);
+    }
+  }
+
+  #[test]
+  fn normalize_shortcut_accepts_symbol_keys() {
+    assert_eq!(normalize_shortcut("alt+-").unwrap(), "Alt+-");
+    assert_eq!(normalize_shortcut("Ctrl++").unwrap(), "Ctrl++");
+  }
+
+  #[test]
+  fn normalize_shortcut_rejects_invalid_input() {
+    for input in ["Ctrl+", "Hyper+K", "Ctrl+Foo+S"] {
+      assert!(normalize_shortcut(input).is_err(), "{}", input);
+    }
+  }
+
+  fn keybinding(action: &str, context: KeybindingContext, shortcut: &str) -> Keybinding {
+    Keybinding {
+      action: action.to_string(),
+      context,
+      shortcut: shortcut.to_string(),
+    }
+  }
+
+  #[test]
+  fn find_keybinding_conflict_detects_same_context() {
+    let keybindings = [
+      keybinding("flow_editor.save", KeybindingContext::FlowEditor, "Ctrl+S"),
+      keybinding("flow_editor.open", KeybindingContext::FlowEditor, "Ctrl+O"),
+    ];
+    let conflict = find_keybinding_conflict(&keybindings, "flow_editor.open", KeybindingContext::FlowEditor, "Ctrl+S");
+    assert_eq!(conflict.map(|k| k.action.as_str()), Some("flow_editor.save"));
+    // 自分自身への再割り当ては衝突しない
+    assert!(find_keybinding_conflict(&keybindings, "flow_editor.save", KeybindingContext::FlowEditor, "Ctrl+S").is_none());
+  }
+
+  #[test]
+  fn find_keybinding_conflict_allows_other_contexts() {
+    let keybindings = [keybinding("flow_editor.new", KeybindingContext::FlowEditor, "Ctrl+N")];
+    assert!(find_keybinding_conflict(&keybindings, "project_tree.new_file", KeybindingContext::ProjectTree, "Ctrl+N").is_none());
+  }
+
+  #[test]
+  fn find_keybinding_conflict_treats_global_as_overlapping_everything() {
+    let keybindings = [
+      keybinding("app.search", KeybindingContext::Global, "Ctrl+K"),
+      keybinding("flow_editor.new", KeybindingContext::FlowEditor, "Ctrl+N"),
+    ];
+    assert!(find_keybinding_conflict(&keybindings, "project_tree.refresh", KeybindingContext::ProjectTree, "Ctrl+K").is_some());
+    assert!(find_keybinding_conflict(&keybindings, "app.new", KeybindingContext::Global, "Ctrl+N").is_some());
+    assert!(find_keybinding_conflict(&keybindings, "project_tree.refresh", KeybindingContext::ProjectTree, "").is_none());
+  }
+
+  #[test]
+  fn reset_all_keeps_user_data() {
+    let mut entries = serde_json::Map::new();
+    entries.insert(String::from("projects"), json!([{ "name": "顧客データ", "filepath": "/data/customers", "remarks": "" }]));
+    entries.insert(String::from("window_state"), json!({ "width": 1 }));
+    let original = entries.clone();
+
+    let targets = reset_targets(ResetScope::All).unwrap();
+    let keys: Vec<&str> = targets.iter().map(|(key, _)| key.as_str()).collect();
+    for kept in ["projects", "recent_projects", "workspace", "custom_themes", "settings_sync", "secrets"] {
+      assert!(!keys.contains(&kept), "{}", kept);
+    }
+    assert!(keys.contains(&"window_state") && keys.contains(&"project_config"));
+
+    entries.extend(targets);
+    assert_eq!(entries["projects"], original["projects"]);
+    assert_ne!(entries["window_state"], original["window_state"]);
+  }
+
+  #[test]
+  fn reset_targets_match_scope() {
+    let keys = |scope| reset_targets(scope).unwrap().into_iter().map(|(key, _)| key).collect::<Vec<_>>();
+    assert_eq!(keys(ResetScope::WindowState), ["window_state"]);
+    assert_eq!(keys(ResetScope::WindowConfig), ["window_config"]);
+    assert_eq!(keys(ResetScope::ProjectConfig), ["project_config"]);
+  }
+
+  #[test]
+  fn normalize_ratios_absorbs_rounding_error() {
+    let mut horizontal = [33, 33, 33];
+    assert!(normalize_ratios(&mut horizontal));
+    assert_eq!(horizontal, [33, 33, 34]);
+
+    let mut horizontal = [34, 34, 33];
+    assert!(normalize_ratios(&mut horizontal));
+    assert_eq!(horizontal, [34, 33, 33]);
+
+    let mut vertical = [85, 15];
+    assert!(normalize_ratios(&mut vertical));
+    assert_eq!(vertical, [85, 15]);
+  }
+
+  #[test]
+  fn normalize_ratios_rejects_larger_errors() {
+    assert!(!normalize_ratios(&mut [30, 30, 30]));
+    assert!(!normalize_ratios(&mut [50, 48]));
+    assert!(!normalize_ratios(&mut [u32::MAX, 1, 1]));
+  }
+
+  #[test]
+  fn overlap_length_handles_extreme_values() {
+    assert_eq!(overlap_length(0, 800, 100, 1920), 700);
+    assert_eq!(overlap_length(-500, 300, 0, 1920), -200);
+    assert_eq!(overlap_length(i32::MAX, u32::MAX, 0, 1920), 1920 - i64::from(i32::MAX));
+    assert_eq!(overlap_length(i32::MIN, u32::MAX, i32::MIN, u32::MAX), i64::from(u32::MAX));
+  }
+
+  #[test]
+  fn fill_missing_fields_adds_nested_defaults() {
+    let mut current = json!({ "a": 1, "b": { "c": 2 } });
+    let default = json!({ "a": 0, "b": { "c": 0, "d": 3 }, "e": 4 });
+
+    assert!(fill_missing_fields(&mut current, &default));
+    assert_eq!(current, json!({ "a": 1, "b": { "c": 2, "d": 3 }, "e": 4 }));
+    assert!(!fill_missing_fields(&mut current, &default));
+  }
+
+  #[test]
+  fn fill_missing_fields_ignores_non_objects() {
+    let mut current = json!([1, 2]);
+    assert!(!fill_missing_fields(&mut current, &json!({ "a": 1 })));
+    assert_eq!(current, json!([1, 2]));
+  }
+
+  #[test]
+  fn migrate_entries_fills_missing_fields() {
+    let mut window_state = json!(Config::default().window_state);
+    window_state.as_object_mut().unwrap().remove("theme");
+    let mut entries = serde_json::Map::new();
+    entries.insert(String::from("window_state"), window_state);
+
+    let entries = migrated(entries);
+    assert_eq!(entries["window_state"]["theme"], json!(Config::default().window_state.theme));
+    assert_eq!(entries["schema_version"], json!(CURRENT_SCHEMA_VERSION));
+  }
+
+  #[test]
+  fn migrate_entries_leaves_newer_schema_untouched() {
+    let mut entries = serde_json::Map::new();
+    entries.insert(String::from("schema_version"), json!(CURRENT_SCHEMA_VERSION + 1));
+    entries.insert(String::from("window_state"), json!({ "width": 1 }));
+    let original = entries.clone();
+
+    assert!(!migrate_entries(&mut entries).unwrap());
+    assert_eq!(entries, original);
+  }
+
+  #[test]
+  fn migrate_entries_moves_legacy_project_config_to_projects() {
+    let mut entries = serde_json::Map::new();
+    entries.insert(String::from("project_config"), json!({ "name": "旧プロジェクト", "filepath": "/data/old", "remarks": "" }));
+
+    let entries = migrated(entries);
+    let projects: Vec<ProjectConfig> = serde_json::from_value(entries["projects"].clone()).unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].name, "旧プロジェクト");
+    assert_eq!(projects[0].filepath, "/data/old");
+    assert_eq!(entries["project_config"]["tags"], json!([]));
+  }
+
+  #[test]
+  fn migrate_entries_skips_unnamed_legacy_project() {
+    let mut entries = serde_json::Map::new();
+    entries.insert(String::from("project_config"), json!({ "name": " ", "filepath": "", "remarks": "" }));
+
+    assert_eq!(migrated(entries)["projects"], json!([]));
+  }
+
+  #[test]
+  fn migrate_entries_is_idempotent() {
+    let mut entries = serde_json::Map::new();
+    entries.insert(String::from("project_config"), json!({ "name": "旧プロジェクト", "filepath": "/data/old", "remarks": "" }));
+    entries.insert(String::from("window_state"), json!({ "width": 800 }));
+
+    let once = migrated(entries);
+    let twice = migrated(once.clone());
+    assert_eq!(once, twice);
+  }
+
+  fn recent(entries: &[(&str, &str)]) -> Vec<RecentProject> {
+    entries
+      .iter()
+      .map(|(name, filepath)| RecentProject {
+        name: name.to_string(),
+        filepath: filepath.to_string(),
+        opened_at: String::new(),
+      })
+      .collect()
+  }
+
+  fn filepaths(recent: &[RecentProject]) -> Vec<&str> {
+    recent.iter().map(|r| r.filepath.as_str()).collect()
+  }
+
+  #[test]
+  fn retarget_recent_removes_deleted_project() {
+    let mut list = recent(&[("A", "/data/a"), ("B", "/data/b")]);
+    retarget_recent(&mut list, "/data/a", None);
+    assert_eq!(filepaths(&list), ["/data/b"]);
+  }
+
+  #[test]
+  fn retarget_recent_follows_rename_and_move() {
+    let mut list = recent(&[("B", "/data/b"), ("A", "/data/a")]);
+    retarget_recent(&mut list, "/data/a", Some(("A2", "/data/a2")));
+    assert_eq!(filepaths(&list), ["/data/b", "/data/a2"]);
+    assert_eq!(list[1].name, "A2");
+  }
+
+  #[test]
+  fn retarget_recent_keeps_newest_when_paths_merge() {
+    let mut list = recent(&[("A", "/data/a"), ("B", "/data/b")]);
+    retarget_recent(&mut list, "/data/a", Some(("A", "/data/b")));
+    assert_eq!(filepaths(&list), ["/data/b"]);
+    assert_eq!(list[0].name, "A");
+  }
+}
@@ -0,0 +1,149 @@
+//! OS のロケール・地域書式を検出するモジュール
+//! インポート/エクスポートの書式初期値や、初回起動時の表示言語の決定に使う
+
+use log::info;
+use serde::Serialize;
+
+/// システムロケール情報
+#[derive(Serialize, Clone, Debug)]
+pub struct SystemLocale {
+  pub locale: String,              // BCP 47 形式のロケール（例: "ja-JP"）
+  pub language: String,            // 言語コード（例: "ja"）
+  pub region: Option<String>,      // 地域コード（例: "JP"）
+  pub decimal_separator: String,   // 小数点記号
+  pub thousands_separator: String, // 桁区切り記号
+  pub first_day_of_week: String,   // 週の始まり（"sunday" / "monday" / "saturday"）
+}
+
+// 小数点にカンマ、桁区切りにピリオドを使う主な地域（例: 1.234,5）
+const DOT_GROUPING_REGIONS: &[&str] = &["AR", "BE", "BR", "CL", "CO", "DE", "DK", "ES", "GR", "HR", "ID", "IS", "IT", "NL", "RO", "RS", "SI", "TR", "VN"];
+
+// 小数点にカンマ、桁区切りに空白を使う主な地域（例: 1 234,5）
+const SPACE_GROUPING_REGIONS: &[&str] = &["AT", "BG", "CZ", "EE", "FI", "FR", "HU", "KZ", "LT", "LV", "NO", "PL", "PT", "RU", "SE", "SK", "UA", "ZA"];
+
+// 地域の既定と異なる言語（言語, 地域, 小数点記号, 桁区切り記号）
+const LANGUAGE_SEPARATORS: &[(&str, &str, &str, &str)] = &[("fr", "BE", ",", " "), ("fr", "CA", ",", " "), ("de", "CH", ".", "’"), ("it", "CH", ".", "’")];
+
+// 週の始まりが日曜日の主な地域
+const SUNDAY_FIRST_REGIONS: &[&str] = &["BR", "CA", "CN", "HK", "IL", "IN", "JP", "KR", "MX", "PH", "TW", "US", "ZA"];
+
+// 週の始まりが土曜日の主な地域
+const SATURDAY_FIRST_REGIONS: &[&str] = &["AE", "AF", "BH", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SA", "SY"];
+
+/// 言語と地域から小数点記号と桁区切り記号を決める
+/// 桁区切りに空白を使う地域（CLDR では改行なしの空白）は、データ中で一般的な通常の空白を返す
+fn separators(language: &str, region: &str) -> (&'static str, &'static str) {
+  if let Some((_, _, decimal, thousands)) = LANGUAGE_SEPARATORS.iter().find(|(l, r, _, _)| *l == language && *r == region) {
+    return (decimal, thousands);
+  }
+  if DOT_GROUPING_REGIONS.contains(&region) {
+    (",", ".")
+  } else if SPACE_GROUPING_REGIONS.contains(&region) {
+    (",", " ")
+  } else {
+    (".", ",")
+  }
+}
+
+/// ロケール文字列から地域書式を決定する
+/// "ja_JP.UTF-8" のような POSIX 形式も BCP 47 形式に揃える
+/// 言語コードとして読めない文字列は "en" とみなす
+pub fn describe_locale(raw: &str) -> SystemLocale {
+  // エンコーディング・修飾子を除去し、区切りをハイフンに統一
+  let tag = raw.trim().split(['.', '@']).next().unwrap_or("").replace('_', "-");
+  let mut parts = tag.split('-');
+  let language = parts
+    .next()
+    .filter(|s| (2..=3).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphabetic()))
+    .unwrap_or("en")
+    .to_lowercase();
+  // 2文字の英字または3桁の数字を地域コードとみなす（スクリプト部 "Hans" などは読み飛ばす）
+  let region = parts
+    .find(|p| (p.len() == 2 && p.chars().all(|c| c.is_ascii_alphabetic())) || (p.len() == 3 && p.chars().all(|c| c.is_ascii_digit())))
+    .map(|p| p.to_uppercase());
+
+  let region_code = region.as_deref().unwrap_or("");
+  let (decimal_separator, thousands_separator) = separators(&language, region_code);
+  let first_day_of_week = if SUNDAY_FIRST_REGIONS.contains(&region_code) {
+    "sunday"
+  } else if SATURDAY_FIRST_REGIONS.contains(&region_code) {
+    "saturday"
+  } else {
+    "monday"
+  };
+
+  SystemLocale {
+    locale: match &region {
+      Some(r) => format!("{}-{}", language, r),
+      None => language.clone(),
+    },
+    language,
+    region,
+    decimal_separator: decimal_separator.to_string(),
+    thousands_separator: thousands_separator.to_string(),
+    first_day_of_week: first_day_of_week.to_string(),
+  }
+}
+
+/// OS のロケールと地域書式を取得するコマンド
+/// 検出できない場合は "en-US" とみなす
+///
+/// # 戻り値
+/// * `SystemLocale` - 言語、地域、小数点記号、週の始まり
+#[tauri::command]
+pub fn get_system_locale() -> SystemLocale {
+  let raw = sys_locale::get_locale().unwrap_or_else(|| String::from("en-US"));
+  let locale = describe_locale(&raw);
+  info!("システムロケールを検出しました: {} -> {:?}", raw, locale);
+  locale
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn summary(raw: &str) -> (String, String, String, String) {
+    let locale = describe_locale(raw);
+    (locale.locale, locale.decimal_separator, locale.thousands_separator, locale.first_day_of_week)
+  }
+
+  fn expected(locale: &str, decimal: &str, thousands: &str, first_day: &str) -> (String, String, String, String) {
+    (locale.to_string(), decimal.to_string(), thousands.to_string(), first_day.to_string())
+  }
+
+  #[test]
+  fn describe_locale_maps_common_locales() {
+    assert_eq!(summary("ja-JP"), expected("ja-JP", ".", ",", "sunday"));
+    assert_eq!(summary("en-US"), expected("en-US", ".", ",", "sunday"));
+    assert_eq!(summary("de-DE"), expected("de-DE", ",", ".", "monday"));
+    assert_eq!(summary("fr-FR"), expected("fr-FR", ",", " ", "monday"));
+    assert_eq!(summary("ru-RU"), expected("ru-RU", ",", " ", "monday"));
+    assert_eq!(summary("sv-SE"), expected("sv-SE", ",", " ", "monday"));
+  }
+
+  #[test]
+  fn describe_locale_uses_language_specific_separators() {
+    assert_eq!(summary("nl-BE"), expected("nl-BE", ",", ".", "monday"));
+    assert_eq!(summary("fr-BE"), expected("fr-BE", ",", " ", "monday"));
+    assert_eq!(summary("en-CA"), expected("en-CA", ".", ",", "sunday"));
+    assert_eq!(summary("fr-CA"), expected("fr-CA", ",", " ", "sunday"));
+    assert_eq!(summary("de-CH"), expected("de-CH", ".", "’", "monday"));
+  }
+
+  #[test]
+  fn describe_locale_accepts_posix_and_script_tags() {
+    assert_eq!(summary("ja_JP.UTF-8"), expected("ja-JP", ".", ",", "sunday"));
+    assert_eq!(summary("zh-Hans-CN"), expected("zh-CN", ".", ",", "sunday"));
+    assert_eq!(summary("ar_SA@calendar=islamic"), expected("ar-SA", ".", ",", "saturday"));
+  }
+
+  #[test]
+  fn describe_locale_falls_back_for_malformed_tags() {
+    for raw in ["", "not a locale!", "1234"] {
+      let locale = describe_locale(raw);
+      assert_eq!(locale.language, "en", "{}", raw);
+      assert_eq!(locale.region, None, "{}", raw);
+      assert_eq!((locale.decimal_separator.as_str(), locale.thousands_separator.as_str()), (".", ","), "{}", raw);
+    }
+  }
+}
@@ -1,8 +1,13 @@
 use std::{
-  sync::{Arc, Mutex},
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+    Mutex,
+  },
   time::{Duration, Instant},
 };
 
+use serde::{Deserialize, Serialize};
 use sysinfo::{Pid, System};
 
 // システム情報の構造体定義
@@ -19,6 +24,36 @@ pub struct SystemInfo {
 // システム情報を定期的に更新するためのグローバル状態
 static SYSTEM_INFO: once_cell::sync::Lazy<Arc<Mutex<Option<SystemInfo>>>> = once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(None)));
 
+// システム情報の更新間隔（ミリ秒）。パフォーマンスモードに応じて変更される
+static MONITORING_INTERVAL_MS: AtomicU64 = AtomicU64::new(2000);
+
+/// パフォーマンスモード
+/// 監視間隔などバックグラウンド処理の負荷をまとめて切り替える
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PerformanceMode {
+  Performance, // 応答性優先（監視間隔: 1秒）
+  #[default]
+  Balanced, // 標準（監視間隔: 2秒）
+  Quiet,       // 省電力優先（監視間隔: 10秒）
+}
+
+impl PerformanceMode {
+  /// モードに対応するシステム情報の更新間隔
+  pub fn monitoring_interval(&self) -> Duration {
+    match self {
+      PerformanceMode::Performance => Duration::from_secs(1),
+      PerformanceMode::Balanced => Duration::from_secs(2),
+      PerformanceMode::Quiet => Duration::from_secs(10),
+    }
+  }
+}
+
+/// パフォーマンスモードを監視ループに反映する
+pub fn apply_performance_mode(mode: PerformanceMode) {
+  MONITORING_INTERVAL_MS.store(mode.monitoring_interval().as_millis() as u64, Ordering::Relaxed);
+}
+
 /// システム情報（CPU・メモリ使用率）を取得するコマンド
 /// フロントエンドから定期的に呼び出してステータス表示に使用
 ///
@@ -55,8 +90,8 @@ pub async fn start_system_monitoring() {
   tokio::time::sleep(Duration::from_millis(200)).await;
 
   loop {
-    // 2秒間隔に変更してCPU負荷を軽減
-    if last_update.elapsed() >= Duration::from_secs(2) {
+    // パフォーマンスモードで指定された間隔で更新してCPU負荷を軽減
+    if last_update.elapsed() >= Duration::from_millis(MONITORING_INTERVAL_MS.load(Ordering::Relaxed)) {
       sys.refresh_cpu();
       sys.refresh_memory();
       sys.refresh_processes();
@@ -0,0 +1,204 @@
+//! 書き込み可否の事前チェックモジュール
+//! エクスポートやプロジェクトフォルダへの書き込みを始める前に書き込み先を検証し、
+//! 途中で失敗する代わりに原因と対処方法を示すエラーを返す
+//! - 保存先フォルダの有無・読み取り専用・アクセス権
+//! - OneDrive / Dropbox のオンライン専用ファイル（プレースホルダー）
+//! - システムフォルダやクラウド同期フォルダへの書き込みの警告
+
+use std::{
+  fs::{self, OpenOptions},
+  io::ErrorKind,
+  path::{Path, PathBuf},
+};
+
+use log::warn;
+use serde::Serialize;
+
+use crate::path_utils;
+
+// Windows のファイル属性: 内容がローカルにない（クラウドからの取得が必要）ことを示すもの
+#[cfg(windows)]
+const FILE_ATTRIBUTE_OFFLINE: u32 = 0x0000_1000;
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x0004_0000;
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+
+/// 書き込みできない理由
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WriteAccessError {
+  InvalidPath { message: String },   // パスの形式が正しくない
+  ParentMissing { path: String },    // 保存先フォルダが存在しない
+  ReadOnly { path: String },         // 読み取り専用になっている
+  PermissionDenied { path: String }, // アクセス権がない（他のアプリが使用中の場合を含む）
+  CloudPlaceholder { path: String }, // オンライン専用ファイル（まだダウンロードされていない）
+}
+
+impl WriteAccessError {
+  /// 利用者向けの対処方法
+  pub fn hint(&self) -> &'static str {
+    match self {
+      WriteAccessError::InvalidPath { .. } => "ファイル名の末尾の空白やピリオドを取り除いてください",
+      WriteAccessError::ParentMissing { .. } => "保存先のフォルダを作成するか、別のフォルダを選んでください",
+      WriteAccessError::ReadOnly { .. } => "ファイルのプロパティで「読み取り専用」を解除するか、別の名前で保存してください",
+      WriteAccessError::PermissionDenied { .. } => "ファイルを開いているアプリを閉じるか、書き込み権限のあるフォルダを選んでください",
+      WriteAccessError::CloudPlaceholder { .. } => "エクスプローラーで「このデバイス上に常に保持する」を選び、ダウンロードが終わってから再実行してください",
+    }
+  }
+}
+
+impl std::fmt::Display for WriteAccessError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let message = match self {
+      WriteAccessError::InvalidPath { message } => message.clone(),
+      WriteAccessError::ParentMissing { path } => format!("保存先フォルダが存在しません: {}", path),
+      WriteAccessError::ReadOnly { path } => format!("読み取り専用のため書き込めません: {}", path),
+      WriteAccessError::PermissionDenied { path } => format!("書き込み権限がありません: {}", path),
+      WriteAccessError::CloudPlaceholder { path } => format!("オンライン専用ファイルのため書き込めません: {}", path),
+    };
+    write!(f, "{}（{}）", message, self.hint())
+  }
+}
+
+impl std::error::Error for WriteAccessError {}
+
+/// 書き込み先チェックの結果（フロントエンド向け）
+#[derive(Serialize, Clone, Debug)]
+pub struct WriteAccessReport {
+  pub writable: bool,                  // 書き込み可能かどうか
+  pub error: Option<WriteAccessError>, // 書き込めない理由
+  pub hint: Option<String>,            // 対処方法
+  pub warnings: Vec<String>,           // 書き込みはできるが注意が必要な点
+}
+
+/// ファイルの内容がローカルにない（オンライン専用）かどうか
+#[cfg(windows)]
+fn is_cloud_placeholder(path: &Path) -> bool {
+  use std::os::windows::fs::MetadataExt;
+  match fs::symlink_metadata(path) {
+    Ok(metadata) => metadata.file_attributes() & (FILE_ATTRIBUTE_OFFLINE | FILE_ATTRIBUTE_RECALL_ON_OPEN | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS) != 0,
+    Err(_) => false,
+  }
+}
+
+/// ファイルの内容がローカルにない（オンライン専用）かどうか
+/// Windows 以外ではプレースホルダーを判定できないため常に false
+#[cfg(not(windows))]
+fn is_cloud_placeholder(_path: &Path) -> bool {
+  false
+}
+
+/// 書き込み先のファイルに書き込めるか事前に確認する
+/// ファイルが既にあれば上書きできるか、なければ保存先フォルダにファイルを作れるかを調べる
+pub fn check_writable(target: &Path) -> Result<(), WriteAccessError> {
+  let display = target.display().to_string();
+  path_utils::validate(target).map_err(|e| WriteAccessError::InvalidPath { message: e.to_string() })?;
+  let target = path_utils::for_io(target);
+
+  // 既存ファイルの上書き
+  if let Ok(metadata) = fs::metadata(&target) {
+    if is_cloud_placeholder(&target) {
+      return Err(WriteAccessError::CloudPlaceholder { path: display });
+    }
+    if metadata.permissions().readonly() {
+      return Err(WriteAccessError::ReadOnly { path: display });
+    }
+    return match OpenOptions::new().append(true).open(&target) {
+      Ok(_) => Ok(()),
+      Err(_) => Err(WriteAccessError::PermissionDenied { path: display }),
+    };
+  }
+
+  // 新規作成: 保存先フォルダに一時ファイルを作って確認
+  let parent = match target.parent().filter(|p| !p.as_os_str().is_empty()) {
+    Some(parent) => parent.to_path_buf(),
+    None => PathBuf::from("."),
+  };
+  let parent_display = parent.display().to_string();
+  if !parent.is_dir() {
+    return Err(WriteAccessError::ParentMissing { path: parent_display });
+  }
+  if is_cloud_placeholder(&parent) {
+    return Err(WriteAccessError::CloudPlaceholder { path: parent_display });
+  }
+  let probe = parent.join(format!(".d4cs-write-test-{}", std::process::id()));
+  match OpenOptions::new().write(true).create_new(true).open(&probe) {
+    Ok(_) => {
+      let _ = fs::remove_file(&probe);
+      Ok(())
+    },
+    Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(()),
+    Err(_) => Err(WriteAccessError::PermissionDenied { path: parent_display }),
+  }
+}
+
+/// システムフォルダの一覧（Windows では Program Files と Windows フォルダ）
+pub fn system_dirs() -> Vec<PathBuf> {
+  if cfg!(windows) {
+    ["ProgramFiles", "ProgramFiles(x86)", "SystemRoot"]
+      .iter()
+      .filter_map(|name| std::env::var_os(name).map(PathBuf::from))
+      .collect()
+  } else {
+    ["/bin", "/etc", "/usr", "/System", "/Library"].iter().map(PathBuf::from).collect()
+  }
+}
+
+/// システムフォルダやクラウド同期フォルダへの書き込みに対する警告
+pub fn location_warnings(target: &Path) -> Vec<String> {
+  let mut warnings = Vec::new();
+  let normalized = path_utils::normalize(target);
+
+  if system_dirs().iter().any(|dir| normalized.starts_with(dir)) {
+    warnings.push(String::from(
+      "システムフォルダへの書き込みです。管理者権限が必要になるか、書き込みが別の場所へ仮想化される場合があります",
+    ));
+  }
+
+  let in_sync_folder = normalized.components().any(|c| {
+    let name = c.as_os_str().to_string_lossy();
+    name.starts_with("OneDrive") || name == "Dropbox"
+  });
+  if in_sync_folder {
+    warnings.push(String::from(
+      "クラウド同期フォルダへの書き込みです。同期中はファイルがロックされたり、競合コピーが作られる場合があります",
+    ));
+  }
+  warnings
+}
+
+/// 書き込み先を検証し、結果と警告をまとめる
+pub fn inspect(target: &Path) -> WriteAccessReport {
+  let warnings = location_warnings(target);
+  match check_writable(target) {
+    Ok(()) => WriteAccessReport {
+      writable: true,
+      error: None,
+      hint: None,
+      warnings,
+    },
+    Err(e) => {
+      warn!("書き込み先の事前チェックに失敗しました: {}", e);
+      WriteAccessReport {
+        writable: false,
+        hint: Some(e.hint().to_string()),
+        error: Some(e),
+        warnings,
+      }
+    },
+  }
+}
+
+/// 書き込み先に書き込めるか事前に確認するコマンド
+/// エクスポートや保存を始める前に呼び出し、書き込めない場合は理由と対処方法を表示する
+///
+/// # 引数
+/// * `path` - 書き込み予定のファイルパス
+///
+/// # 戻り値
+/// * 書き込み可否、理由、対処方法、警告
+#[tauri::command]
+pub fn check_write_access(path: String) -> WriteAccessReport {
+  inspect(Path::new(&path))
+}
@@ -3,6 +3,7 @@ import "./App.css";
 import ConsoleMsg from "./utils/ConsoleMsg";
 import { loadStore, saveStore, getProjectConfig } from "./utils/StoreManager";
 import { increaseFontSize, decreaseFontSize, resetFontSize, restoreFontSize } from "./utils/fontSizeManager";
+import { watchAccessibilityPreferences } from "./utils/accessibilityManager";
 import WindowTitlebar from "./components/WindowTitlebar/windowTitlebar";
 import MainContent from "./components/MainContent/mainContent";
 import Statusbar from "./components/Statusbar/statusbar";
@@ -107,6 +108,14 @@ function App() {
     init();
   }, []); // 空の依存配列で初回マウント時のみ実行
 
+  // OS のアクセシビリティ設定（アニメーションを減らす・ハイコントラスト）を反映
+  useEffect(() => {
+    const cleanup = watchAccessibilityPreferences();
+    return () => {
+      cleanup.then((stop) => stop());
+    };
+  }, []);
+
   // キーボードイベントリスナーの登録・削除
   useEffect(() => {
     // キーボードイベントリスナーを追加
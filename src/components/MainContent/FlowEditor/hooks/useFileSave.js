@@ -13,6 +13,7 @@
 import { useState, useCallback, useRef } from "react";
 import { save } from "@tauri-apps/plugin-dialog";
 import { writeTextFile } from "@tauri-apps/plugin-fs";
+import { invoke } from "@tauri-apps/api/core";
 import { appDataDir, join } from "@tauri-apps/api/path";
 import ConsoleMsg from "../../../../utils/ConsoleMsg";
 
@@ -62,10 +63,11 @@ export const useFileSave = ({ exportFlowData, getNodes, getEdges, nodeCounter, i
 
   /**
    * デフォルトの保存パスを取得
+   * ポータブルモードではユーザーフォルダに書き込まないよう、設定ディレクトリ（実行ファイルの隣の data/）を使う
    */
   const getDefaultSavePath = useCallback(async (fileName) => {
     try {
-      const appDir = await appDataDir();
+      const appDir = (await invoke("is_portable_mode")) ? await invoke("get_config_dir") : await appDataDir();
       const srcPath = await join(appDir, "src");
       return await join(srcPath, `${fileName}.d4flow`);
     } catch (error) {
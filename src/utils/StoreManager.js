@@ -1,17 +1,27 @@
 import { load } from "@tauri-apps/plugin-store";
-import { configDir } from "@tauri-apps/api/path";
+import { invoke } from "@tauri-apps/api/core";
+import { join } from "@tauri-apps/api/path";
 import ConsoleMsg from "./ConsoleMsg";
 
 const STORE_FILE = "D4CleaningStudio.config";
 
+/**
+ * 設定ファイルのパスを取得
+ * 設定ディレクトリはバックエンドが決定する（--config-dir 引数・D4CS_CONFIG_DIR・ポータブルモードを反映）
+ * @returns {Promise<string>} 設定ファイルのパス
+ */
+async function getStorePath() {
+  const dir = await invoke("get_config_dir");
+  return await join(dir, STORE_FILE);
+}
+
 /**
  * ストアインスタンスを取得
  * @returns {Promise<Store>} ストアインスタンス
  */
 async function getStore() {
   try {
-    const dir = `${await configDir()}\\D4CleaningStudio`;
-    return await load(`${dir}\\${STORE_FILE}`);
+    return await load(await getStorePath());
   } catch (error) {
     ConsoleMsg("error", `ストア取得エラー: ${error}`);
     throw error;
@@ -25,11 +35,11 @@ async function getStore() {
 export async function loadStore() {
   try {
     ConsoleMsg("info", "ストアから設定を読み込み開始");
-    // 設定ディレクトリのパス
-    const dir = `${await configDir()}\\D4CleaningStudio`;
-    // ストアファイルをオープン（なければ自動生成）
-    const store = await load(`${dir}\\${STORE_FILE}`);
-    ConsoleMsg("debug", `ストアパス: ${dir}\\${STORE_FILE}`);
+    // 設定ファイルのパス
+    const path = await getStorePath();
+    // ストアファイルをオープン（起動時にバックエンドが読み込んだものを共有）
+    const store = await load(path);
+    ConsoleMsg("debug", `ストアパス: ${path}`);
 
     // キー取得（未定義なら空オブジェクト）
     const projectConfig = (await store.get("project_config")) ?? {};
@@ -46,22 +56,21 @@ export async function loadStore() {
 }
 
 /**
- * STORE プラグイン経由で設定を保存する
+ * バックエンドの set_many コマンド経由で設定を保存する
+ * 設定ファイルへの書き込みはバックエンドのアトミックな保存処理に一本化しているため、
+ * ストアへ直接 set / save はしない
  * @param {{projectConfig: object, windowConfig: object, windowState: object}} config
  */
 export async function saveStore({ projectConfig, windowConfig, windowState }) {
   try {
     ConsoleMsg("info", "ストアへの設定保存開始");
-    const dir = `${await configDir()}\\D4CleaningStudio`;
-    const store = await load(`${dir}\\${STORE_FILE}`);
-    ConsoleMsg("debug", `ストアパス: ${dir}\\${STORE_FILE}`);
-
-    // キーを書き込み
-    await store.set("project_config", projectConfig);
-    await store.set("window_config", windowConfig);
-    await store.set("window_state", windowState);
-    // 永続化
-    await store.save();
+    await invoke("set_many", {
+      updates: {
+        project_config: projectConfig,
+        window_config: windowConfig,
+        window_state: windowState,
+      },
+    });
     ConsoleMsg("info", "ストアへの保存が完了しました");
   } catch (error) {
     ConsoleMsg("error", `ストア保存エラー: ${error}`);
@@ -84,15 +93,29 @@ export async function loadMainPanelLayout() {
   );
 }
 
+/**
+ * パネル比率を合計 100 の整数に丸める
+ * 丸めで生じた誤差は最も大きいパネルで吸収する
+ * @param {number[]} ratios
+ * @returns {number[]}
+ */
+function roundRatios(ratios) {
+  const ints = ratios.map((n) => Math.round(n));
+  const remainder = 100 - ints.reduce((sum, n) => sum + n, 0);
+  const largest = ints.indexOf(Math.max(...ints));
+  ints[largest] += remainder;
+  return ints;
+}
+
 /**
  * メインパネルレイアウトをストアへ保存する
  * @param {number[]} horizontal
  * @param {number[]} vertical
  */
 export async function saveMainPanelLayout(horizontal, vertical) {
-  // フロート値を整数に丸めてから保存
-  const hInts = horizontal.map((n) => Math.round(n));
-  const vInts = vertical.map((n) => Math.round(n));
+  // フロート値を合計 100 の整数に丸めてから保存
+  const hInts = roundRatios(horizontal);
+  const vInts = roundRatios(vertical);
   const cfg = await loadStore();
   const newCfg = {
     ...cfg,
@@ -164,9 +187,9 @@ export async function setProjectConfig(projectConfig) {
 
     // 不要なフィールドを削除して保存
     const cleanConfig = {
-      name: projectConfig.name,
+      name: projectConfig.name ?? "",
       description: projectConfig.description,
-      filepath: projectConfig.filepath,
+      filepath: projectConfig.filepath ?? "",
       createdAt: projectConfig.createdAt,
       version: projectConfig.version || "1.0.0",
       type: projectConfig.type || "D4CleaningStudio",
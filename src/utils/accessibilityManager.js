@@ -0,0 +1,80 @@
+/**
+ * アクセシビリティ設定管理ユーティリティ
+ *
+ * OS の「アニメーションを減らす」「ハイコントラスト」設定を取得し、
+ * ルート要素の data 属性（data-reduced-motion / data-high-contrast）に反映する。
+ * Rust 側の検出結果と WebView のメディアクエリのどちらかが有効なら有効とみなす。
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import ConsoleMsg from "./ConsoleMsg";
+
+/**
+ * Rust 側から送信される設定変更イベント名
+ */
+const ACCESSIBILITY_CHANGED_EVENT = "accessibility-changed";
+
+/**
+ * WebView 側で参照するメディアクエリ
+ */
+const MEDIA_QUERIES = {
+  reducedMotion: ["(prefers-reduced-motion: reduce)"],
+  highContrast: ["(forced-colors: active)", "(prefers-contrast: more)"],
+};
+
+/**
+ * メディアクエリのいずれかが一致しているかを判定する
+ *
+ * @param {MediaQueryList[]} queries - 判定対象のメディアクエリ
+ * @returns {boolean} いずれかが一致していれば true
+ */
+const matchesAny = (queries) => queries.some((query) => query.matches);
+
+/**
+ * アクセシビリティ設定の監視を開始する
+ * 起動時に現在の設定を反映し、OS・WebView どちらの変更にも追従する
+ *
+ * @returns {Promise<Function>} 監視を終了するクリーンアップ関数
+ */
+export const watchAccessibilityPreferences = async () => {
+  const reducedMotionQueries = MEDIA_QUERIES.reducedMotion.map((query) => window.matchMedia(query));
+  const highContrastQueries = MEDIA_QUERIES.highContrast.map((query) => window.matchMedia(query));
+  const allQueries = [...reducedMotionQueries, ...highContrastQueries];
+
+  // Rust 側で検出した OS の設定（取得できない場合はメディアクエリのみで判定）
+  let osPreferences = { reduced_motion: false, high_contrast: false };
+
+  const apply = () => {
+    const reducedMotion = osPreferences.reduced_motion || matchesAny(reducedMotionQueries);
+    const highContrast = osPreferences.high_contrast || matchesAny(highContrastQueries);
+    document.documentElement.dataset.reducedMotion = String(reducedMotion);
+    document.documentElement.dataset.highContrast = String(highContrast);
+  };
+
+  try {
+    osPreferences = await invoke("get_accessibility_preferences");
+    ConsoleMsg("info", "OS のアクセシビリティ設定を取得しました", osPreferences);
+  } catch (error) {
+    ConsoleMsg("warn", "OS のアクセシビリティ設定の取得に失敗しました", error);
+  }
+  apply();
+
+  allQueries.forEach((query) => query.addEventListener("change", apply));
+
+  let unlisten = () => {};
+  try {
+    unlisten = await listen(ACCESSIBILITY_CHANGED_EVENT, (event) => {
+      osPreferences = event.payload;
+      ConsoleMsg("info", "OS のアクセシビリティ設定が変更されました", osPreferences);
+      apply();
+    });
+  } catch (error) {
+    ConsoleMsg("warn", "アクセシビリティ設定の変更通知の登録に失敗しました", error);
+  }
+
+  return () => {
+    allQueries.forEach((query) => query.removeEventListener("change", apply));
+    unlisten();
+  };
+};